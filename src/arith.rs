@@ -0,0 +1,165 @@
+//! Arithmetic for the spots that can overflow on large inputs.
+//!
+//! By default these are the plain operators, which wrap silently in release builds. With the
+//! `checked` feature every operation is checked, and an overflow panics with the operation, its
+//! operands and the caller's location.
+
+use std::{
+    fmt::Display,
+    ops::{Add, Mul, Shl, Sub},
+};
+
+/// Integer types with checked operations.
+pub trait Integer:
+    Copy
+    + Display
+    + Add<Output = Self>
+    + Sub<Output = Self>
+    + Mul<Output = Self>
+    + Shl<u32, Output = Self>
+{
+    const NAME: &'static str;
+    const ZERO: Self;
+    const ONE: Self;
+
+    fn checked_add(self, rhs: Self) -> Option<Self>;
+    fn checked_sub(self, rhs: Self) -> Option<Self>;
+    fn checked_mul(self, rhs: Self) -> Option<Self>;
+    fn checked_shl(self, rhs: u32) -> Option<Self>;
+}
+
+macro_rules! impl_integer {
+    ($($ty:ty),*) => {
+        $(
+            impl Integer for $ty {
+                const NAME: &'static str = stringify!($ty);
+                const ZERO: Self = 0;
+                const ONE: Self = 1;
+
+                fn checked_add(self, rhs: Self) -> Option<Self> {
+                    <$ty>::checked_add(self, rhs)
+                }
+
+                fn checked_sub(self, rhs: Self) -> Option<Self> {
+                    <$ty>::checked_sub(self, rhs)
+                }
+
+                fn checked_mul(self, rhs: Self) -> Option<Self> {
+                    <$ty>::checked_mul(self, rhs)
+                }
+
+                fn checked_shl(self, rhs: u32) -> Option<Self> {
+                    // The std version only checks the shift amount, not the bits shifted out
+                    let ret = <$ty>::checked_shl(self, rhs)?;
+                    (ret >> rhs == self).then_some(ret)
+                }
+            }
+        )*
+    };
+}
+
+impl_integer!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
+
+#[cold]
+#[track_caller]
+fn overflow<T: Integer>(a: T, op: &str, b: impl Display) -> ! {
+    panic!(
+        "arithmetic overflow: {a} {op} {b} does not fit in {}",
+        T::NAME
+    )
+}
+
+#[inline]
+#[track_caller]
+pub fn add<T: Integer>(a: T, b: T) -> T {
+    if cfg!(feature = "checked") {
+        a.checked_add(b).unwrap_or_else(|| overflow(a, "+", b))
+    } else {
+        a + b
+    }
+}
+
+#[inline]
+#[track_caller]
+pub fn sub<T: Integer>(a: T, b: T) -> T {
+    if cfg!(feature = "checked") {
+        a.checked_sub(b).unwrap_or_else(|| overflow(a, "-", b))
+    } else {
+        a - b
+    }
+}
+
+#[inline]
+#[track_caller]
+pub fn mul<T: Integer>(a: T, b: T) -> T {
+    if cfg!(feature = "checked") {
+        a.checked_mul(b).unwrap_or_else(|| overflow(a, "*", b))
+    } else {
+        a * b
+    }
+}
+
+/// `a << bits`. Checked mode also catches set bits being shifted out.
+#[inline]
+#[track_caller]
+pub fn shl<T: Integer>(a: T, bits: u32) -> T {
+    if cfg!(feature = "checked") {
+        a.checked_shl(bits)
+            .unwrap_or_else(|| overflow(a, "<<", bits))
+    } else {
+        a << bits
+    }
+}
+
+#[track_caller]
+pub fn sum<T: Integer>(values: impl IntoIterator<Item = T>) -> T {
+    let mut ret = T::ZERO;
+    for v in values {
+        ret = add(ret, v);
+    }
+    ret
+}
+
+#[track_caller]
+pub fn product<T: Integer>(values: impl IntoIterator<Item = T>) -> T {
+    let mut ret = T::ONE;
+    for v in values {
+        ret = mul(ret, v);
+    }
+    ret
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn arith_in_range() {
+        assert_eq!(add(2u8, 3), 5);
+        assert_eq!(sub(2i32, 3), -1);
+        assert_eq!(mul(u64::MAX / 2, 2), u64::MAX - 1);
+        assert_eq!(shl(1usize, 63), 1 << 63);
+        assert_eq!(sum([1u32, 2, 3]), 6);
+        assert_eq!(product([2u32, 3, 4]), 24);
+        assert_eq!(sum::<u8>([]), 0);
+    }
+
+    #[test]
+    fn arith_checked_shl() {
+        assert_eq!(Integer::checked_shl(0b11u8, 6), Some(0b1100_0000));
+        assert_eq!(Integer::checked_shl(0b11u8, 7), None);
+        assert_eq!(Integer::checked_shl(1u8, 8), None);
+    }
+
+    #[cfg(feature = "checked")]
+    #[test]
+    fn arith_reports_overflow() {
+        let err = std::panic::catch_unwind(|| mul(1u64 << 40, 1 << 30)).unwrap_err();
+        assert_eq!(
+            err.downcast_ref::<String>().map(String::as_str),
+            Some("arithmetic overflow: 1099511627776 * 1073741824 does not fit in u64")
+        );
+        assert!(std::panic::catch_unwind(|| sum([usize::MAX, 1])).is_err());
+        assert!(std::panic::catch_unwind(|| shl(3u32, 31)).is_err());
+    }
+}
@@ -0,0 +1,202 @@
+use std::{
+    collections::HashMap,
+    io::Write,
+    path::Path,
+    time::{Duration, SystemTime},
+};
+
+use crate::days::{Solve, Solved};
+
+pub const HISTORY_FILE: &str = "bench_history.csv";
+
+/// The `part` of records that time parsing.
+pub const PARSE: u8 = 0;
+
+/// A single timing measurement, stored as one line of the history file.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Record {
+    pub commit: String,
+    pub timestamp: u64,
+    pub year: u16,
+    pub day: u8,
+    /// 1 or 2, or [`PARSE`] for the time spent parsing.
+    pub part: u8,
+    pub nanos: u64,
+}
+
+impl Record {
+    pub fn new(commit: &str, year: u16, day: u8, part: u8, elapsed: Duration) -> Self {
+        Self {
+            commit: commit.to_owned(),
+            timestamp: SystemTime::now()
+                .duration_since(SystemTime::UNIX_EPOCH)
+                .map_or(0, |v| v.as_secs()),
+            year,
+            day,
+            part,
+            nanos: elapsed.as_nanos().try_into().unwrap_or(u64::MAX),
+        }
+    }
+
+    fn from_line(line: &str) -> Option<Self> {
+        let fields: Vec<_> = line.split(',').collect();
+        let (year, rest) = match fields[..] {
+            [commit, timestamp, year, day, part, nanos] => {
+                (year.parse().ok()?, [commit, timestamp, day, part, nanos])
+            }
+            // Written before the runner knew about years
+            [commit, timestamp, day, part, nanos] => (2023, [commit, timestamp, day, part, nanos]),
+            _ => return None,
+        };
+        let [commit, timestamp, day, part, nanos] = rest;
+        Some(Self {
+            commit: commit.to_owned(),
+            timestamp: timestamp.parse().ok()?,
+            year,
+            day: day.parse().ok()?,
+            part: part.parse().ok()?,
+            nanos: nanos.parse().ok()?,
+        })
+    }
+
+    fn to_line(&self) -> String {
+        format!(
+            "{},{},{},{},{},{}",
+            self.commit, self.timestamp, self.year, self.day, self.part, self.nanos
+        )
+    }
+}
+
+#[derive(Debug)]
+pub struct Regression {
+    pub year: u16,
+    pub day: u8,
+    pub part: u8,
+    pub baseline: Duration,
+    pub current: Duration,
+    pub percent: f64,
+}
+
+/// Reads the history file, ignoring lines it cannot make sense of. A missing file is an empty
+/// history.
+pub fn load_history(path: impl AsRef<Path>) -> std::io::Result<Vec<Record>> {
+    match std::fs::read_to_string(path) {
+        Ok(data) => Ok(data.lines().filter_map(Record::from_line).collect()),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Vec::new()),
+        Err(e) => Err(e),
+    }
+}
+
+pub fn append_history(path: impl AsRef<Path>, records: &[Record]) -> std::io::Result<()> {
+    let mut file = std::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)?;
+    for record in records {
+        writeln!(file, "{}", record.to_line())?;
+    }
+    Ok(())
+}
+
+/// The most recent measurement of every year/day/part in the history.
+pub fn baseline(history: &[Record]) -> HashMap<(u16, u8, u8), &Record> {
+    let mut ret = HashMap::new();
+    for record in history {
+        ret.insert((record.year, record.day, record.part), record);
+    }
+    ret
+}
+
+/// Compares `current` against the baseline and returns every part that got slower by more than
+/// `threshold` percent. Parts without a baseline are never regressions.
+pub fn find_regressions(history: &[Record], current: &[Record], threshold: f64) -> Vec<Regression> {
+    let baseline = baseline(history);
+    current
+        .iter()
+        .filter_map(|cur| {
+            let base = baseline.get(&(cur.year, cur.day, cur.part))?;
+            let percent = if base.nanos == 0 {
+                0.0
+            } else {
+                (cur.nanos as f64 - base.nanos as f64) * 100.0 / base.nanos as f64
+            };
+            (percent > threshold).then(|| Regression {
+                year: cur.year,
+                day: cur.day,
+                part: cur.part,
+                baseline: Duration::from_nanos(base.nanos),
+                current: Duration::from_nanos(cur.nanos),
+                percent,
+            })
+        })
+        .collect()
+}
+
+/// Short hash of the checked out commit, or `"unknown"` outside of a git checkout.
+pub fn current_commit() -> String {
+    std::process::Command::new("git")
+        .args(["rev-parse", "--short", "HEAD"])
+        .output()
+        .ok()
+        .filter(|out| out.status.success())
+        .and_then(|out| String::from_utf8(out.stdout).ok())
+        .map(|v| v.trim().to_owned())
+        .unwrap_or_else(|| "unknown".to_owned())
+}
+
+/// Solves `runs` times and returns the answers along with the fastest time of every step.
+pub fn measure(solve: Solve, input: &str, runs: usize) -> Solved {
+    let mut best = Solved::run(solve, input);
+    for _ in 1..runs {
+        let cur = Solved::run(solve, input);
+        best.parse = best.parse.zip(cur.parse).map(|(a, b)| a.min(b));
+        for (best, (_, cur)) in best.parts.iter_mut().zip(cur.parts) {
+            best.1 = best.1.min(cur);
+        }
+    }
+    best
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(commit: &str, day: u8, part: u8, nanos: u64) -> Record {
+        Record {
+            commit: commit.to_owned(),
+            timestamp: 1,
+            year: 2023,
+            day,
+            part,
+            nanos,
+        }
+    }
+
+    #[test]
+    fn bench_record_roundtrip() {
+        let rec = record("abc123", 5, 2, 1234);
+        assert_eq!(Record::from_line(&rec.to_line()), Some(rec.clone()));
+        assert_eq!(Record::from_line("abc,1,2"), None);
+        assert_eq!(Record::from_line("abc,1,2,3,4,5,6"), None);
+        assert_eq!(Record::from_line("abc123,1,5,2,1234"), Some(rec));
+    }
+
+    #[test]
+    fn bench_baseline_is_latest() {
+        let history = [record("a", 1, 1, 100), record("b", 1, 1, 200)];
+        assert_eq!(baseline(&history)[&(2023, 1, 1)].commit, "b");
+    }
+
+    #[test]
+    fn bench_regressions() {
+        let history = [record("a", 1, 1, 100), record("a", 1, 2, 100)];
+        let current = [
+            record("b", 1, 1, 109),
+            record("b", 1, 2, 111),
+            record("b", 2, 1, 1000),
+        ];
+        let res = find_regressions(&history, &current, 10.0);
+        assert_eq!(res.len(), 1);
+        assert_eq!((res[0].day, res[0].part), (1, 2));
+    }
+}
@@ -0,0 +1,397 @@
+//! The registry of all solved puzzles, across years.
+
+use std::{
+    fmt::Display,
+    path::Path,
+    time::{Duration, Instant},
+};
+
+/// Solves both parts of a day for the given input, reporting every step as it starts and finishes.
+pub type Solve = fn(&str, &mut dyn FnMut(Event));
+
+/// Parses the input and wraps it in the day's REPL commands.
+pub type ReplFn = for<'a> fn(&'a str) -> Box<dyn crate::repl::Session + 'a>;
+
+/// Runs the day's parser on the input without solving anything, panicking if it is malformed.
+pub type ValidateFn = fn(&str);
+
+/// Generates an input of the given size, in whatever measure is natural for the day: lines, or
+/// the side of a grid.
+pub type GenerateFn = fn(usize, &mut crate::scale::Rng) -> String;
+
+/// Explains how the given part of the day got its answer for the input.
+pub type ExplainFn = fn(&str, u8) -> crate::explain::Table;
+
+/// A solved puzzle, as seen by the runner.
+pub struct Day {
+    pub year: u16,
+    pub day: u8,
+    pub title: &'static str,
+    pub solve: Solve,
+    pub repl: Option<ReplFn>,
+    pub explain: Option<ExplainFn>,
+    pub validate: Option<ValidateFn>,
+    pub format: Option<crate::inspect::Format>,
+    pub generate: Option<GenerateFn>,
+    /// Whether the day spreads its work over [`par`](crate::par) workers.
+    pub parallel: bool,
+}
+
+impl Day {
+    /// Where the input is read from. 2023 inputs used to live in `input/dayNN.txt`, and are still
+    /// found there when `input/2023/dayNN.txt` doesn't exist.
+    pub fn input_path(&self) -> String {
+        self.input_path_in(Path::new(""))
+    }
+
+    /// Like [`Self::input_path`], looking for the files under `root`. The returned path is still
+    /// relative to it.
+    fn input_path_in(&self, root: &Path) -> String {
+        let path = self.default_input_path();
+        match self.old_input_path() {
+            Some(old) if !root.join(&path).exists() && root.join(&old).exists() => old,
+            _ => path,
+        }
+    }
+
+    /// Where the input should be: `input/YEAR/dayNN.txt`.
+    fn default_input_path(&self) -> String {
+        format!("input/{}/day{:02}.txt", self.year, self.day)
+    }
+
+    /// Where the input used to be, for the days that had another location.
+    fn old_input_path(&self) -> Option<String> {
+        (self.year == 2023).then(|| format!("input/day{:02}.txt", self.day))
+    }
+
+    /// The known answers for the input, one part per line.
+    pub fn answers_path(&self) -> String {
+        format!("input/{}/day{:02}.answers", self.year, self.day)
+    }
+}
+
+impl std::fmt::Display for Day {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} day {}", self.year, self.day)
+    }
+}
+
+/// A step of solving a day.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Step {
+    Parse,
+    Part(u8),
+}
+
+impl std::fmt::Display for Step {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Parse => write!(f, "parse"),
+            Self::Part(part) => write!(f, "part {part}"),
+        }
+    }
+}
+
+/// What a [`Solve`] function reports while it runs.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum Event {
+    Started(Step),
+    Finished {
+        step: Step,
+        /// Only parts have an answer.
+        answer: Option<String>,
+        elapsed: Duration,
+        /// Allocations made by the whole process while the step ran.
+        allocations: usize,
+    },
+}
+
+/// The answers of one run of a day, and how long every step took.
+#[derive(Clone, Debug)]
+pub struct Solved {
+    /// Time spent parsing, or `None` for days that parse the input in each part.
+    pub parse: Option<Duration>,
+    /// The answer and solve time of part 1 and part 2.
+    pub parts: [(String, Duration); 2],
+}
+
+impl Solved {
+    /// Runs `solve` to completion on the current thread.
+    pub fn run(solve: Solve, data: &str) -> Self {
+        let mut ret = Self {
+            parse: None,
+            parts: Default::default(),
+        };
+        solve(data, &mut |event| match event {
+            Event::Finished {
+                step: Step::Parse,
+                elapsed,
+                ..
+            } => ret.parse = Some(elapsed),
+            Event::Finished {
+                step: Step::Part(part),
+                answer,
+                elapsed,
+                ..
+            } => {
+                ret.parts[usize::from(part - 1)] = (answer.unwrap_or_default(), elapsed);
+            }
+            Event::Started(_) => (),
+        });
+        ret
+    }
+}
+
+/// Parses `data` once and runs both parts on the result.
+pub fn parsed<'a, T, A: Display, B: Display>(
+    data: &'a str,
+    report: &mut dyn FnMut(Event),
+    parse: impl FnOnce(&'a str) -> T,
+    part1: impl FnOnce(&T) -> A,
+    part2: impl FnOnce(&T) -> B,
+) {
+    let input = timed(report, Step::Parse, |_| None, || parse(data));
+    part(report, 1, || part1(&input));
+    part(report, 2, || part2(&input));
+}
+
+/// Runs both parts on the raw input.
+pub fn unparsed<A: Display, B: Display>(
+    data: &str,
+    report: &mut dyn FnMut(Event),
+    part1: impl FnOnce(&str) -> A,
+    part2: impl FnOnce(&str) -> B,
+) {
+    part(report, 1, || part1(data));
+    part(report, 2, || part2(data));
+}
+
+fn part<A: Display>(report: &mut dyn FnMut(Event), part: u8, solve: impl FnOnce() -> A) {
+    timed(
+        report,
+        Step::Part(part),
+        |answer: &String| Some(answer.clone()),
+        || solve().to_string(),
+    );
+}
+
+/// Runs `f` as `step`, with `answer` picking the answer to report from its result.
+fn timed<T>(
+    report: &mut dyn FnMut(Event),
+    step: Step,
+    answer: impl FnOnce(&T) -> Option<String>,
+    f: impl FnOnce() -> T,
+) -> T {
+    report(Event::Started(step));
+    let allocations = crate::alloc::allocations();
+    let start = Instant::now();
+    let ret = f();
+    let elapsed = start.elapsed();
+    report(Event::Finished {
+        step,
+        answer: answer(&ret),
+        elapsed,
+        allocations: crate::alloc::allocations() - allocations,
+    });
+    ret
+}
+
+/// Registers a day. The module must have a `TITLE` and `part1` and `part2` taking the input, and
+/// may opt in to more with flags after the module name:
+///
+/// - `parse`: the module has `parse`, and both parts take a reference to what it returns.
+/// - `repl`: the module has `repl`, see [`ReplFn`].
+/// - `validate`: the module has `validate`, see [`ValidateFn`]. Days with `parse` get one that
+///   runs it.
+/// - `format`: the module has a `FORMAT` describing its input, see [`Format`](crate::inspect::Format).
+/// - `generate`: the module has `generate`, see [`GenerateFn`].
+/// - `explain`: the module has `explain`, see [`ExplainFn`].
+/// - `parallel`: the parts use [`par`](crate::par), so the runner can compare against one thread.
+macro_rules! day {
+    (@solve $module:ident, parse $(, $flag:ident)*) => {
+        |data, report| {
+            $crate::days::parsed(
+                data,
+                report,
+                $module::parse,
+                |input| $module::part1(input),
+                |input| $module::part2(input),
+            )
+        }
+    };
+    (@solve $module:ident, $other:ident $(, $flag:ident)*) => {
+        $crate::days::day!(@solve $module $(, $flag)*)
+    };
+    (@solve $module:ident) => {
+        |data, report| $crate::days::unparsed(data, report, $module::part1, $module::part2)
+    };
+    (@repl $module:ident, repl $(, $flag:ident)*) => {
+        Some($module::repl)
+    };
+    (@repl $module:ident, $other:ident $(, $flag:ident)*) => {
+        $crate::days::day!(@repl $module $(, $flag)*)
+    };
+    (@repl $module:ident) => {
+        None
+    };
+    (@validate $module:ident, parse $(, $flag:ident)*) => {
+        Some(|data| drop($module::parse(data)))
+    };
+    (@validate $module:ident, validate $(, $flag:ident)*) => {
+        Some($module::validate)
+    };
+    (@validate $module:ident, $other:ident $(, $flag:ident)*) => {
+        $crate::days::day!(@validate $module $(, $flag)*)
+    };
+    (@validate $module:ident) => {
+        None
+    };
+    (@format $module:ident, format $(, $flag:ident)*) => {
+        Some($module::FORMAT)
+    };
+    (@format $module:ident, $other:ident $(, $flag:ident)*) => {
+        $crate::days::day!(@format $module $(, $flag)*)
+    };
+    (@format $module:ident) => {
+        None
+    };
+    (@generate $module:ident, generate $(, $flag:ident)*) => {
+        Some($module::generate)
+    };
+    (@generate $module:ident, $other:ident $(, $flag:ident)*) => {
+        $crate::days::day!(@generate $module $(, $flag)*)
+    };
+    (@generate $module:ident) => {
+        None
+    };
+    (@explain $module:ident, explain $(, $flag:ident)*) => {
+        Some($module::explain)
+    };
+    (@explain $module:ident, $other:ident $(, $flag:ident)*) => {
+        $crate::days::day!(@explain $module $(, $flag)*)
+    };
+    (@explain $module:ident) => {
+        None
+    };
+    (@parallel parallel $(, $flag:ident)*) => {
+        true
+    };
+    (@parallel $other:ident $(, $flag:ident)*) => {
+        $crate::days::day!(@parallel $($flag),*)
+    };
+    (@parallel) => {
+        false
+    };
+    ($year:literal, $day:literal, $module:ident $(, $flag:ident)*) => {
+        $crate::days::Day {
+            year: $year,
+            day: $day,
+            title: $module::TITLE,
+            solve: $crate::days::day!(@solve $module $(, $flag)*),
+            repl: $crate::days::day!(@repl $module $(, $flag)*),
+            explain: $crate::days::day!(@explain $module $(, $flag)*),
+            generate: $crate::days::day!(@generate $module $(, $flag)*),
+            validate: $crate::days::day!(@validate $module $(, $flag)*),
+            format: $crate::days::day!(@format $module $(, $flag)*),
+            parallel: $crate::days::day!(@parallel $($flag),*),
+        }
+    };
+}
+
+pub(crate) use day;
+
+/// The days of every year, oldest year first.
+pub static YEARS: &[&[Day]] = &[crate::y2023::DAYS];
+
+pub fn all() -> impl Iterator<Item = &'static Day> {
+    YEARS.iter().flat_map(|days| days.iter())
+}
+
+pub fn year(year: u16) -> impl Iterator<Item = &'static Day> {
+    all().filter(move |d| d.year == year)
+}
+
+pub fn latest_year() -> u16 {
+    all().map(|d| d.year).max().unwrap_or(0)
+}
+
+pub fn find(year: u16, day: u8) -> Option<&'static Day> {
+    all().find(|d| d.year == year && d.day == day)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn days_registry_sorted_and_unique() {
+        let keys: Vec<_> = all().map(|d| (d.year, d.day)).collect();
+        assert!(keys.windows(2).all(|v| v[0] < v[1]), "{keys:?}");
+        assert_eq!(
+            find(2023, 5).map(|d| d.default_input_path()).as_deref(),
+            Some("input/2023/day05.txt")
+        );
+        assert!(find(2023, 26).is_none());
+    }
+
+    #[test]
+    fn days_old_input_path() {
+        let root = std::env::temp_dir().join(format!("aoc23-input-{}", std::process::id()));
+        std::fs::create_dir_all(root.join("input/2023")).unwrap();
+        let day = find(2023, 5).unwrap();
+        assert_eq!(day.input_path_in(&root), "input/2023/day05.txt");
+
+        std::fs::write(root.join("input/day05.txt"), "old").unwrap();
+        assert_eq!(day.input_path_in(&root), "input/day05.txt");
+
+        std::fs::write(root.join("input/2023/day05.txt"), "new").unwrap();
+        assert_eq!(day.input_path_in(&root), "input/2023/day05.txt");
+
+        std::fs::remove_dir_all(&root).unwrap();
+    }
+
+    fn sum_product(data: &str, report: &mut dyn FnMut(Event)) {
+        parsed(
+            data,
+            report,
+            |data| crate::parse::integers::<u32>(data).unwrap(),
+            |v| v[0] + v[1],
+            |v| v[0] * v[1],
+        );
+    }
+
+    #[test]
+    fn days_solved() {
+        let mut events = Vec::new();
+        sum_product("3 4", &mut |event| events.push(event));
+        let steps: Vec<_> = events
+            .iter()
+            .map(|event| match event {
+                Event::Started(step) => format!("start {step}"),
+                Event::Finished { step, answer, .. } => format!("{step}: {answer:?}"),
+            })
+            .collect();
+        assert_eq!(
+            steps,
+            [
+                "start parse",
+                "parse: None",
+                "start part 1",
+                "part 1: Some(\"7\")",
+                "start part 2",
+                "part 2: Some(\"12\")"
+            ]
+        );
+
+        let solved = Solved::run(sum_product, "3 4");
+        assert!(solved.parse.is_some());
+        assert_eq!(solved.parts.map(|(answer, _)| answer), ["7", "12"]);
+
+        let solved = Solved::run(
+            |data, report| unparsed(data, report, str::len, str::to_uppercase),
+            "ab",
+        );
+        assert!(solved.parse.is_none());
+        assert_eq!(solved.parts.map(|(answer, _)| answer), ["2", "AB"]);
+    }
+}
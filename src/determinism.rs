@@ -0,0 +1,216 @@
+//! Checking that answers don't depend on hash map iteration order or on the number of worker
+//! threads, by running a day repeatedly with different map seeds and thread counts.
+
+use std::fmt;
+
+use crate::{
+    days::{ExplainFn, Solve, Solved},
+    hash, par,
+};
+
+/// The settings of one run.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Variant {
+    pub seed: u64,
+    pub threads: usize,
+}
+
+impl Variant {
+    /// The first run uses the defaults, later ones spread out over seeds and thread counts.
+    fn nth(i: usize) -> Self {
+        Self {
+            seed: (i as u64).wrapping_mul(0x9e37_79b9_7f4a_7c15),
+            threads: [1, 2, 3, 5][i % 4],
+        }
+    }
+}
+
+impl fmt::Display for Variant {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "map seed {:#x} on {} threads", self.seed, self.threads)
+    }
+}
+
+/// An output that changed between runs.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Mismatch {
+    /// What differs, like "part 2 answer".
+    pub what: String,
+    pub expected: (Variant, String),
+    pub actual: (Variant, String),
+}
+
+impl fmt::Display for Mismatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} differs: {:?} with {}, {:?} with {}",
+            self.what, self.expected.1, self.expected.0, self.actual.1, self.actual.0
+        )
+    }
+}
+
+/// Everything a run outputs, with a name for every output.
+fn outputs(solve: Solve, explain: Option<ExplainFn>, data: &str) -> Vec<(String, String)> {
+    let solved = Solved::run(solve, data);
+    let mut ret: Vec<_> = (1..)
+        .zip(solved.parts)
+        .map(|(part, (answer, _))| (format!("part {part} answer"), answer))
+        .collect();
+    if let Some(explain) = explain {
+        for part in 1..=2 {
+            ret.push((
+                format!("part {part} explanation"),
+                explain(data, part).json(),
+            ));
+        }
+    }
+    ret
+}
+
+/// Runs the day `runs` times on the current thread and compares every run's answers and
+/// explanations with the first's. Leaves the map seed and thread count at their defaults.
+pub fn check(
+    solve: Solve,
+    explain: Option<ExplainFn>,
+    data: &str,
+    runs: usize,
+) -> Result<(), Mismatch> {
+    let _lock = par::lock_settings();
+
+    let mut expected = None;
+    let mut res = Ok(());
+    for variant in (0..runs).map(Variant::nth) {
+        hash::set_map_seed(variant.seed);
+        par::set_threads(variant.threads);
+        let cur = outputs(solve, explain, data);
+        let Some((first, expected)) = &expected else {
+            expected = Some((variant, cur));
+            continue;
+        };
+        let mismatch = expected.iter().zip(cur).find(|(a, b)| a.1 != b.1);
+        if let Some(((what, a), (_, b))) = mismatch {
+            res = Err(Mismatch {
+                what: what.clone(),
+                expected: (*first, a.clone()),
+                actual: (variant, b),
+            });
+            break;
+        }
+    }
+    hash::set_map_seed(0);
+    par::set_threads(0);
+    res
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{days, hash::FastSet, scale::Rng};
+
+    /// Answers with the first value the set iterates over.
+    fn first_in_set(data: &str, report: &mut dyn FnMut(days::Event)) {
+        days::unparsed(
+            data,
+            report,
+            |data| data.len(),
+            |data| {
+                let set: FastSet<&str> = data.split(' ').collect();
+                set.into_iter().next().unwrap_or_default().to_owned()
+            },
+        );
+    }
+
+    #[test]
+    fn determinism_detects_order() {
+        let data = "a b c d e f g h i j k l m n o p";
+        let mismatch = check(first_in_set, None, data, 8).unwrap_err();
+        assert_eq!(mismatch.what, "part 2 answer");
+        assert_eq!(mismatch.expected.0, Variant::nth(0));
+        assert_ne!(mismatch.expected.1, mismatch.actual.1);
+    }
+
+    /// Inputs valid for both parts, for the days without a generator.
+    const EXAMPLES: &[(u8, &str)] = &[
+        (
+            2,
+            "Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green\n\
+             Game 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue\n",
+        ),
+        (
+            3,
+            "467..114..\n...*......\n..35..633.\n......#...\n617*......\n",
+        ),
+        (
+            4,
+            "Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53\n\
+             Card 2: 13 32 20 16 61 | 61 30 68 82 17 32 24 19\n\
+             Card 3:  1 21 53 59 44 | 69 82 63 72 16 21 14  1\n\
+             Card 4: 41 92 73 84 69 | 59 84 76 51 58  5 54 83\n\
+             Card 5: 87 83 26 28 32 | 88 30 70 12 93 22 82 36\n\
+             Card 6: 31 18 13 56 72 | 74 77 10 23 35 67 36 11\n",
+        ),
+        (
+            5,
+            "seeds: 79 14 55 13\n\n\
+             seed-to-soil map:\n50 98 2\n52 50 48\n\n\
+             soil-to-location map:\n0 15 37\n37 52 2\n39 0 15\n",
+        ),
+        (6, "Time:      7  15   30\nDistance:  9  40  200\n"),
+        (7, "32T3K 765\nT55J5 684\nKK677 28\nKTJJT 220\nQQQJA 483\n"),
+        (
+            8,
+            "LR\n\n\
+             AAA = (ZZZ, ZZZ)\n11B = (XXX, 11Z)\n11Z = (11B, XXX)\n\
+             22A = (22B, XXX)\n22B = (22C, 22C)\n22C = (22Z, 22Z)\n22Z = (22B, 22B)\n\
+             11A = (11B, XXX)\nZZZ = (ZZZ, ZZZ)\nXXX = (XXX, XXX)\n",
+        ),
+        (10, ".S--7.\n.|..|.\n.L--J.\n"),
+        (
+            12,
+            "???.### 1,1,3\n.??..??...?##. 1,1,3\n?###???????? 3,2,1\n",
+        ),
+        (
+            13,
+            "#.##..##.\n..#.##.#.\n##......#\n##......#\n..#.##.#.\n..##..##.\n#.#.##.#.\n\n\
+              #...##..#\n#....#..#\n..##..###\n#####.##.\n#####.##.\n..##..###\n#....#..#\n",
+        ),
+        (
+            14,
+            "O....#....\nO.OO#....#\n.....##...\nOO.#O....O\n.O.....O#.\n\
+              O.#..O.#.#\n..O..#O..O\n.......O..\n#....###..\n#OO..#....\n",
+        ),
+        (15, "rn=1,cm-,qp=3,cm=2,qp-,pc=4,ot=9,ab=5,pc-,pc=6,ot=7\n"),
+        (
+            18,
+            "R 2 (#000030)\nD 2 (#000021)\nL 2 (#000032)\nU 2 (#000023)\n",
+        ),
+        (
+            19,
+            "in{s<1351:px,A}\npx{a<2006:A,m>2090:A,R}\n\n\
+              {x=787,m=2655,a=1222,s=2876}\n{x=1679,m=44,a=2067,s=496}\n",
+        ),
+        (
+            20,
+            "broadcaster -> a, x\n%a -> b, c\n%b -> c\n&c -> a, n\n&n -> hub\n\
+              %x -> m\n&m -> hub\n&hub -> rx\n",
+        ),
+    ];
+
+    #[test]
+    fn determinism_days() {
+        for day in days::all() {
+            let data = match day.generate {
+                Some(generate) => generate(12, &mut Rng::new(day.day.into())),
+                None => EXAMPLES
+                    .iter()
+                    .find(|v| v.0 == day.day)
+                    .unwrap_or_else(|| panic!("{day} has neither a generator nor an example"))
+                    .1
+                    .to_owned(),
+            };
+            let res = check(day.solve, day.explain, &data, 8);
+            assert_eq!(res, Ok(()), "{day}");
+        }
+    }
+}
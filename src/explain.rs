@@ -0,0 +1,228 @@
+//! Structured explanations of how a day got its answer, one row per line or cell of the input.
+//! Shown as a table or as JSON by `run --explain`.
+
+use std::fmt::{self, Write as _};
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum Value {
+    Int(i128),
+    Text(String),
+    List(Vec<Value>),
+}
+
+macro_rules! impl_from_int {
+    ($($ty:ty),*) => {
+        $(
+            impl From<$ty> for Value {
+                fn from(v: $ty) -> Self {
+                    Self::Int(v.into())
+                }
+            }
+        )*
+    };
+}
+
+impl_from_int!(u8, u16, u32, u64, i8, i16, i32, i64);
+
+impl From<usize> for Value {
+    fn from(v: usize) -> Self {
+        Self::Int(v as i128)
+    }
+}
+
+impl From<&str> for Value {
+    fn from(v: &str) -> Self {
+        Self::Text(v.to_owned())
+    }
+}
+
+impl From<String> for Value {
+    fn from(v: String) -> Self {
+        Self::Text(v)
+    }
+}
+
+impl<T: Into<Value>> From<Vec<T>> for Value {
+    fn from(v: Vec<T>) -> Self {
+        Self::List(v.into_iter().map(Into::into).collect())
+    }
+}
+
+impl fmt::Display for Value {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Int(v) => write!(f, "{v}"),
+            Self::Text(v) => f.write_str(v),
+            Self::List(values) => {
+                for (i, v) in values.iter().enumerate() {
+                    if i > 0 {
+                        f.write_str(" ")?;
+                    }
+                    write!(f, "{v}")?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+impl Value {
+    fn write_json(&self, out: &mut String) {
+        match self {
+            Self::Int(v) => {
+                let _ = write!(out, "{v}");
+            }
+            Self::Text(v) => write_json_string(out, v),
+            Self::List(values) => {
+                out.push('[');
+                for (i, v) in values.iter().enumerate() {
+                    if i > 0 {
+                        out.push_str(", ");
+                    }
+                    v.write_json(out);
+                }
+                out.push(']');
+            }
+        }
+    }
+}
+
+fn write_json_string(out: &mut String, text: &str) {
+    out.push('"');
+    for c in text.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            c if c.is_control() => {
+                let _ = write!(out, "\\u{:04x}", c as u32);
+            }
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+}
+
+/// Rows of values under named columns.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Table {
+    columns: Vec<&'static str>,
+    rows: Vec<Vec<Value>>,
+}
+
+impl Table {
+    pub fn new(columns: &[&'static str]) -> Self {
+        Self {
+            columns: columns.to_vec(),
+            rows: Vec::new(),
+        }
+    }
+
+    /// Adds a row with a value for every column.
+    pub fn row(&mut self, values: impl IntoIterator<Item = Value>) {
+        let values: Vec<_> = values.into_iter().collect();
+        assert_eq!(values.len(), self.columns.len(), "{values:?}");
+        self.rows.push(values);
+    }
+
+    pub fn rows(&self) -> &[Vec<Value>] {
+        &self.rows
+    }
+
+    /// Aligned columns with a header, numbers aligned to the right.
+    pub fn text(&self) -> String {
+        let cells: Vec<Vec<String>> = self
+            .rows
+            .iter()
+            .map(|row| row.iter().map(Value::to_string).collect())
+            .collect();
+        let widths: Vec<usize> = (0..self.columns.len())
+            .map(|i| {
+                cells
+                    .iter()
+                    .map(|row| row[i].chars().count())
+                    .chain([self.columns[i].len()])
+                    .max()
+                    .unwrap_or(0)
+            })
+            .collect();
+
+        let mut ret = String::new();
+        let line = |ret: &mut String, cells: Vec<(&str, bool)>| {
+            let cells: Vec<String> = cells
+                .into_iter()
+                .zip(&widths)
+                .map(|((cell, right), &width)| {
+                    if right {
+                        format!("{cell:>width$}")
+                    } else {
+                        format!("{cell:<width$}")
+                    }
+                })
+                .collect();
+            ret.push_str(cells.join("  ").trim_end());
+            ret.push('\n');
+        };
+        line(&mut ret, self.columns.iter().map(|v| (*v, false)).collect());
+        for (row, values) in cells.iter().zip(&self.rows) {
+            let right = values.iter().map(|v| matches!(v, Value::Int(_)));
+            line(
+                &mut ret,
+                row.iter().map(String::as_str).zip(right).collect(),
+            );
+        }
+        ret
+    }
+
+    /// A JSON array with an object per row, on one line.
+    pub fn json(&self) -> String {
+        let mut ret = String::from("[");
+        for (i, row) in self.rows.iter().enumerate() {
+            if i > 0 {
+                ret.push_str(", ");
+            }
+            ret.push('{');
+            for (j, (column, value)) in self.columns.iter().zip(row).enumerate() {
+                if j > 0 {
+                    ret.push_str(", ");
+                }
+                write_json_string(&mut ret, column);
+                ret.push_str(": ");
+                value.write_json(&mut ret);
+            }
+            ret.push('}');
+        }
+        ret.push(']');
+        ret
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn table() -> Table {
+        let mut ret = Table::new(&["line", "digits", "value"]);
+        ret.row(["1abc2".into(), vec![1, 2].into(), 12.into()]);
+        ret.row(["treb7uchet \"x\"".into(), vec![7].into(), 77.into()]);
+        ret
+    }
+
+    #[test]
+    fn explain_text() {
+        assert_eq!(
+            table().text(),
+            "line            digits  value\n\
+             1abc2           1 2        12\n\
+             treb7uchet \"x\"  7          77\n"
+        );
+    }
+
+    #[test]
+    fn explain_json() {
+        assert_eq!(
+            table().json(),
+            r#"[{"line": "1abc2", "digits": [1, 2], "value": 12}, {"line": "treb7uchet \"x\"", "digits": [7], "value": 77}]"#
+        );
+    }
+}
@@ -0,0 +1,425 @@
+//! Graph algorithms over implicit graphs (nodes plus a neighbour closure) and over graphs given as
+//! adjacency lists of node indices.
+
+use std::{
+    cmp::Reverse,
+    collections::{BinaryHeap, VecDeque},
+    hash::Hash,
+};
+
+use crate::hash::{FastMap, FastSet};
+
+/// Breadth-first search from `start`, returning the distance to every reachable node.
+pub fn bfs<N, I>(start: N, mut neighbours: impl FnMut(&N) -> I) -> FastMap<N, usize>
+where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = N>,
+{
+    let mut dist = FastMap::default();
+    dist.insert(start.clone(), 0);
+    let mut queue = VecDeque::from([(start, 0)]);
+    while let Some((cur, d)) = queue.pop_front() {
+        for next in neighbours(&cur) {
+            if !dist.contains_key(&next) {
+                dist.insert(next.clone(), d + 1);
+                queue.push_back((next, d + 1));
+            }
+        }
+    }
+    dist
+}
+
+/// Breadth-first search for the shortest path (by number of edges) from `start` to a node
+/// accepted by `is_goal`. The path includes both ends.
+pub fn bfs_path<N, I>(
+    start: N,
+    mut neighbours: impl FnMut(&N) -> I,
+    mut is_goal: impl FnMut(&N) -> bool,
+) -> Option<Vec<N>>
+where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = N>,
+{
+    let mut parents: FastMap<N, Option<N>> = FastMap::default();
+    parents.insert(start.clone(), None);
+    let mut queue = VecDeque::from([start]);
+    while let Some(cur) = queue.pop_front() {
+        if is_goal(&cur) {
+            let mut path = vec![cur];
+            while let Some(Some(parent)) = parents.get(path.last().unwrap()) {
+                path.push(parent.clone());
+            }
+            path.reverse();
+            return Some(path);
+        }
+        for next in neighbours(&cur) {
+            if !parents.contains_key(&next) {
+                parents.insert(next.clone(), Some(cur.clone()));
+                queue.push_back(next);
+            }
+        }
+    }
+    None
+}
+
+/// Depth-first search from `start`, returning the reachable nodes in pre-order.
+pub fn dfs<N, I>(start: N, mut neighbours: impl FnMut(&N) -> I) -> Vec<N>
+where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = N>,
+{
+    let mut seen = FastSet::default();
+    let mut order = Vec::new();
+    let mut stack = vec![start];
+    while let Some(cur) = stack.pop() {
+        if !seen.insert(cur.clone()) {
+            continue;
+        }
+        let first = stack.len();
+        stack.extend(neighbours(&cur).into_iter().filter(|v| !seen.contains(v)));
+        // Visit neighbours in the order they were given
+        stack[first..].reverse();
+        order.push(cur);
+    }
+    order
+}
+
+/// Dijkstra's shortest path from `start` to a node accepted by `is_goal`. `neighbours` yields
+/// `(node, cost)` pairs. Returns the total cost and the path, including both ends.
+pub fn dijkstra<N, I>(
+    start: N,
+    neighbours: impl FnMut(&N) -> I,
+    is_goal: impl FnMut(&N) -> bool,
+) -> Option<(u64, Vec<N>)>
+where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = (N, u64)>,
+{
+    astar(start, neighbours, |_| 0, is_goal)
+}
+
+/// A* search. Same as [`dijkstra`], guided by `heuristic`, which must never overestimate the
+/// remaining cost to a goal.
+pub fn astar<N, I>(
+    start: N,
+    mut neighbours: impl FnMut(&N) -> I,
+    mut heuristic: impl FnMut(&N) -> u64,
+    mut is_goal: impl FnMut(&N) -> bool,
+) -> Option<(u64, Vec<N>)>
+where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = (N, u64)>,
+{
+    struct Visit {
+        cost: u64,
+        parent: usize,
+    }
+
+    // Nodes live in `nodes`, everything else refers to them by index so `N` doesn't need `Ord`
+    let mut nodes = vec![start.clone()];
+    let mut index = FastMap::default();
+    index.insert(start.clone(), 0);
+    let mut visits = vec![Visit {
+        cost: 0,
+        parent: usize::MAX,
+    }];
+    let mut heap = BinaryHeap::from([Reverse((heuristic(&start), 0, 0))]);
+
+    while let Some(Reverse((_, cost, cur))) = heap.pop() {
+        if cost > visits[cur].cost {
+            continue;
+        }
+        if is_goal(&nodes[cur]) {
+            let mut path = vec![nodes[cur].clone()];
+            let mut i = cur;
+            while visits[i].parent != usize::MAX {
+                i = visits[i].parent;
+                path.push(nodes[i].clone());
+            }
+            path.reverse();
+            return Some((cost, path));
+        }
+        for (next, step) in neighbours(&nodes[cur]) {
+            let next_cost = cost + step;
+            let i = match index.get(&next) {
+                Some(&i) if visits[i].cost <= next_cost => continue,
+                Some(&i) => {
+                    visits[i] = Visit {
+                        cost: next_cost,
+                        parent: cur,
+                    };
+                    i
+                }
+                None => {
+                    let i = nodes.len();
+                    index.insert(next.clone(), i);
+                    nodes.push(next);
+                    visits.push(Visit {
+                        cost: next_cost,
+                        parent: cur,
+                    });
+                    i
+                }
+            };
+            heap.push(Reverse((next_cost + heuristic(&nodes[i]), next_cost, i)));
+        }
+    }
+    None
+}
+
+/// Tarjan's strongly connected components. Components are returned in reverse topological order:
+/// no component has an edge to a component that comes after it.
+pub fn tarjan_scc(adj: &[Vec<usize>]) -> Vec<Vec<usize>> {
+    const UNVISITED: usize = usize::MAX;
+
+    let mut index = vec![UNVISITED; adj.len()];
+    let mut low = vec![0; adj.len()];
+    let mut on_stack = vec![false; adj.len()];
+    let mut stack = Vec::new();
+    let mut components = Vec::new();
+    let mut next_index = 0;
+
+    for root in 0..adj.len() {
+        if index[root] != UNVISITED {
+            continue;
+        }
+        // (node, next edge to look at), the explicit version of the recursion
+        let mut work = vec![(root, 0)];
+        index[root] = next_index;
+        low[root] = next_index;
+        next_index += 1;
+        stack.push(root);
+        on_stack[root] = true;
+
+        while let Some((node, edge)) = work.last_mut() {
+            let node = *node;
+            if let Some(&next) = adj[node].get(*edge) {
+                *edge += 1;
+                if index[next] == UNVISITED {
+                    index[next] = next_index;
+                    low[next] = next_index;
+                    next_index += 1;
+                    stack.push(next);
+                    on_stack[next] = true;
+                    work.push((next, 0));
+                } else if on_stack[next] {
+                    low[node] = low[node].min(index[next]);
+                }
+                continue;
+            }
+
+            work.pop();
+            if let Some(&(parent, _)) = work.last() {
+                low[parent] = low[parent].min(low[node]);
+            }
+
+            if low[node] == index[node] {
+                let mut component = Vec::new();
+                loop {
+                    let v = stack.pop().unwrap();
+                    on_stack[v] = false;
+                    component.push(v);
+                    if v == node {
+                        break;
+                    }
+                }
+                components.push(component);
+            }
+        }
+    }
+    components
+}
+
+/// Kahn's topological sort. Returns `None` if the graph has a cycle.
+pub fn toposort(adj: &[Vec<usize>]) -> Option<Vec<usize>> {
+    let mut in_degree = vec![0; adj.len()];
+    for &next in adj.iter().flatten() {
+        in_degree[next] += 1;
+    }
+    let mut queue: VecDeque<_> = (0..adj.len()).filter(|&v| in_degree[v] == 0).collect();
+    let mut order = Vec::with_capacity(adj.len());
+    while let Some(cur) = queue.pop_front() {
+        order.push(cur);
+        for &next in &adj[cur] {
+            in_degree[next] -= 1;
+            if in_degree[next] == 0 {
+                queue.push_back(next);
+            }
+        }
+    }
+    (order.len() == adj.len()).then_some(order)
+}
+
+/// The cycle reached by repeatedly applying a function: after `start` steps the values repeat
+/// every `len` steps.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Cycle {
+    pub start: usize,
+    pub len: usize,
+}
+
+impl Cycle {
+    /// The smallest number of steps that yields the same value as taking `steps` steps.
+    pub fn equivalent_steps(&self, steps: usize) -> usize {
+        if steps < self.start {
+            steps
+        } else {
+            self.start + (steps - self.start) % self.len
+        }
+    }
+}
+
+/// Brent's cycle detection on the sequence `x0, f(x0), f(f(x0)), ...`. Only loops forever if the
+/// sequence never repeats.
+pub fn find_cycle<T: Clone + PartialEq>(x0: T, mut f: impl FnMut(&T) -> T) -> Cycle {
+    let mut power = 1;
+    let mut len = 1;
+    let mut tortoise = x0.clone();
+    let mut hare = f(&x0);
+    while tortoise != hare {
+        if power == len {
+            tortoise = hare.clone();
+            power *= 2;
+            len = 0;
+        }
+        hare = f(&hare);
+        len += 1;
+    }
+
+    let mut tortoise = x0.clone();
+    let mut hare = x0;
+    for _ in 0..len {
+        hare = f(&hare);
+    }
+    let mut start = 0;
+    while tortoise != hare {
+        tortoise = f(&tortoise);
+        hare = f(&hare);
+        start += 1;
+    }
+    Cycle { start, len }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn grid_neighbours(
+        grid: &'static [&'static str],
+    ) -> impl FnMut(&(usize, usize)) -> Vec<(usize, usize)> {
+        move |&(x, y)| {
+            let mut ret = Vec::new();
+            let open = |x: usize, y: usize| {
+                grid.get(y)
+                    .and_then(|row| row.as_bytes().get(x))
+                    .is_some_and(|&b| b != b'#')
+            };
+            for (dx, dy) in [(1, 0), (0, 1), (usize::MAX, 0), (0, usize::MAX)] {
+                let (nx, ny) = (x.wrapping_add(dx), y.wrapping_add(dy));
+                if open(nx, ny) {
+                    ret.push((nx, ny));
+                }
+            }
+            ret
+        }
+    }
+
+    const MAZE: &[&str] = &["..#....", ".##.##.", "....#..", "#.#...#"];
+
+    #[test]
+    fn graph_bfs() {
+        let dist = bfs((0, 0), grid_neighbours(MAZE));
+        assert_eq!(dist[&(0, 0)], 0);
+        assert_eq!(dist[&(3, 0)], 7);
+        assert_eq!(dist[&(6, 0)], 10);
+        assert!(!dist.contains_key(&(2, 0)));
+    }
+
+    #[test]
+    fn graph_bfs_path() {
+        let path = bfs_path((0, 0), grid_neighbours(MAZE), |&p| p == (3, 1)).unwrap();
+        assert_eq!(
+            path,
+            [(0, 0), (0, 1), (0, 2), (1, 2), (2, 2), (3, 2), (3, 1)]
+        );
+        assert_eq!(
+            bfs_path((0, 0), grid_neighbours(MAZE), |&p| p == (2, 0)),
+            None
+        );
+    }
+
+    #[test]
+    fn graph_dfs() {
+        let adj = [vec![1, 2], vec![3], vec![3], vec![]];
+        assert_eq!(dfs(0, |&v| adj[v].clone()), [0, 1, 3, 2]);
+    }
+
+    #[test]
+    fn graph_dijkstra() {
+        // 0 -> 1 is cheap but 1 -> 3 is expensive
+        let adj: [&[(usize, u64)]; 4] = [&[(1, 1), (2, 4)], &[(3, 10)], &[(3, 2)], &[]];
+        let res = dijkstra(0, |&v| adj[v].iter().copied(), |&v| v == 3);
+        assert_eq!(res, Some((6, vec![0, 2, 3])));
+        assert_eq!(dijkstra(3, |&v| adj[v].iter().copied(), |&v| v == 0), None);
+    }
+
+    #[test]
+    fn graph_astar() {
+        let mut neighbours = grid_neighbours(MAZE);
+        let goal = (6, 2);
+        let res = astar(
+            (0, 0),
+            |p| neighbours(p).into_iter().map(|v| (v, 1)),
+            |&(x, y): &(usize, usize)| (x.abs_diff(goal.0) + y.abs_diff(goal.1)) as u64,
+            |&p| p == goal,
+        );
+        let (cost, path) = res.unwrap();
+        assert_eq!(cost, 10);
+        assert_eq!(path.len(), 11);
+        assert_eq!(bfs((0, 0), grid_neighbours(MAZE))[&goal], 10);
+    }
+
+    #[test]
+    fn graph_tarjan_scc() {
+        // 0 <-> 1 -> 2 <-> 3 -> 4, 5 alone
+        let adj = [vec![1], vec![0, 2], vec![3], vec![2, 4], vec![], vec![]];
+        let mut res = tarjan_scc(&adj);
+        res.iter_mut().for_each(|c| c.sort());
+        assert_eq!(res, [vec![4], vec![2, 3], vec![0, 1], vec![5]]);
+    }
+
+    #[test]
+    fn graph_tarjan_scc_cross_edges() {
+        // 1 -> 2 is a cross edge into an already finished component
+        let adj = [vec![2, 1], vec![2, 3], vec![0], vec![1]];
+        let mut res = tarjan_scc(&adj);
+        res.iter_mut().for_each(|c| c.sort());
+        assert_eq!(res, [vec![0, 1, 2, 3]]);
+
+        let adj = [vec![1, 2], vec![], vec![1]];
+        assert_eq!(tarjan_scc(&adj), [vec![1], vec![2], vec![0]]);
+    }
+
+    #[test]
+    fn graph_toposort() {
+        let adj = [vec![2], vec![0, 2], vec![3], vec![]];
+        assert_eq!(toposort(&adj), Some(vec![1, 0, 2, 3]));
+        let adj = [vec![1], vec![2], vec![0]];
+        assert_eq!(toposort(&adj), None);
+    }
+
+    #[test]
+    fn graph_find_cycle() {
+        // 0 1 2 3 4 5 6 7 | 3 4 5 6 7 ...
+        let cycle = find_cycle(0, |&v| if v == 7 { 3 } else { v + 1 });
+        assert_eq!(cycle, Cycle { start: 3, len: 5 });
+        assert_eq!(cycle.equivalent_steps(2), 2);
+        assert_eq!(cycle.equivalent_steps(8), 3);
+        assert_eq!(
+            cycle.equivalent_steps(1_000_000_000),
+            3 + (1_000_000_000 - 3) % 5
+        );
+
+        assert_eq!(find_cycle(5, |&v| v), Cycle { start: 0, len: 1 });
+    }
+}
@@ -0,0 +1,154 @@
+//! Positions on grids stored row by row, and filling the open areas of such grids.
+
+/// A position that may be off the grid, so moving off an edge can be checked afterwards.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct Pos {
+    pub x: isize,
+    pub y: isize,
+}
+
+impl Pos {
+    pub fn new(x: usize, y: usize) -> Self {
+        Self {
+            x: x.try_into().unwrap(),
+            y: y.try_into().unwrap(),
+        }
+    }
+
+    /// The index in a grid `width` wide, or `None` left, right or above it. Positions below the
+    /// grid give an index past its end.
+    pub fn index(self, width: usize) -> Option<usize> {
+        let x = usize::try_from(self.x).ok()?;
+        if x < width {
+            let y = usize::try_from(self.y).ok()?;
+            Some((y * width) + x)
+        } else {
+            None
+        }
+    }
+}
+
+/// A step to one of the four neighbours, with up being towards the first row.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum Dir {
+    L,
+    R,
+    U,
+    D,
+}
+
+impl std::ops::Add<Dir> for Pos {
+    type Output = Pos;
+
+    fn add(mut self, rhs: Dir) -> Self::Output {
+        match rhs {
+            Dir::L => self.x -= 1,
+            Dir::R => self.x += 1,
+            Dir::U => self.y -= 1,
+            Dir::D => self.y += 1,
+        }
+        self
+    }
+}
+
+/// A grid [`flood_fill`] can fill. Positions off the grid are never open.
+pub trait Fill {
+    /// Whether `pos` is open, without filling it.
+    fn can_fill(&self, pos: Pos) -> bool;
+
+    /// Fills `pos` if it is open, and returns whether it was.
+    fn try_fill(&mut self, pos: Pos) -> bool;
+}
+
+/// Fills the open area around `start` a span of a row at a time, without recursion.
+pub fn flood_fill(grid: &mut impl Fill, start: Pos) {
+    let mut pending = vec![(start.x, start.x, start.y, 1)];
+
+    if start.y > 0 {
+        pending.push((start.x, start.x, start.y - 1, -1));
+    }
+
+    while let Some((mut x1, x2, y, dy)) = pending.pop() {
+        let mut x = x1;
+        if grid.can_fill(Pos { x, y }) {
+            while grid.try_fill(Pos { x: x - 1, y }) {
+                x -= 1;
+            }
+            if x < x1 {
+                pending.push((x, x1 - 1, y - dy, -dy));
+            }
+        }
+
+        while x1 <= x2 {
+            while grid.try_fill(Pos { x: x1, y }) {
+                x1 += 1;
+            }
+
+            if x1 > x {
+                pending.push((x, x1 - 1, y + dy, dy));
+            }
+
+            if x1 - 1 > x2 {
+                pending.push((x2 + 1, x1 - 1, y - dy, -dy));
+            }
+
+            x1 += 1;
+
+            while x1 < x2 && !grid.can_fill(Pos { x: x1, y }) {
+                x1 += 1;
+            }
+
+            x = x1;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Chars {
+        data: Vec<u8>,
+        width: usize,
+    }
+
+    impl Fill for Chars {
+        fn can_fill(&self, pos: Pos) -> bool {
+            pos.index(self.width)
+                .and_then(|i| self.data.get(i))
+                .is_some_and(|&v| v == b'.')
+        }
+
+        fn try_fill(&mut self, pos: Pos) -> bool {
+            match pos.index(self.width).and_then(|i| self.data.get_mut(i)) {
+                Some(v @ b'.') => {
+                    *v = b'o';
+                    true
+                }
+                _ => false,
+            }
+        }
+    }
+
+    #[test]
+    fn grid_flood_fill() {
+        let rows = ["..#...", ".#..#.", "#..#..", "...#.#"];
+        let mut grid = Chars {
+            data: rows.concat().into_bytes(),
+            width: 6,
+        };
+        flood_fill(&mut grid, Pos::new(2, 2));
+        let filled: Vec<_> = grid.data.chunks(6).map(|v| v.to_vec()).collect();
+        let expected = ["..#ooo", ".#oo#o", "#oo#oo", "ooo#o#"];
+        assert_eq!(filled, expected.map(|v| v.as_bytes().to_vec()));
+    }
+
+    #[test]
+    fn grid_pos() {
+        assert_eq!(Pos::new(2, 1).index(3), Some(5));
+        assert_eq!((Pos::new(0, 1) + Dir::L).index(3), None);
+        assert_eq!((Pos::new(2, 1) + Dir::R).index(3), None);
+        assert_eq!((Pos::new(2, 0) + Dir::U).index(3), None);
+        assert_eq!((Pos::new(2, 1) + Dir::D).index(3), Some(8));
+    }
+}
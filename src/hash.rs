@@ -0,0 +1,164 @@
+//! A fast hasher with stable output for the solvers' maps and sets.
+//!
+//! This is the FxHash algorithm used inside rustc. It is not resistant to crafted collisions,
+//! which doesn't matter for puzzle inputs. Unlike the std `RandomState` it is not random: maps
+//! and sets start from a seed set with [`set_map_seed`], zero by default, so with the default
+//! seed iteration order and anything derived from hashes is the same on every run.
+//! [`hash_one`] and a plain [`FxHasher`] always use seed zero.
+
+use std::{
+    collections::{HashMap, HashSet},
+    hash::{BuildHasher, Hash, Hasher},
+    sync::atomic::{AtomicU64, Ordering},
+};
+
+const SEED: u64 = 0x51_7c_c1_b7_27_22_0a_95;
+
+#[derive(Clone, Copy, Default, Debug)]
+pub struct FxHasher {
+    hash: u64,
+}
+
+impl FxHasher {
+    #[inline]
+    fn add(&mut self, word: u64) {
+        self.hash = (self.hash.rotate_left(5) ^ word).wrapping_mul(SEED);
+    }
+}
+
+impl Hasher for FxHasher {
+    #[inline]
+    fn write(&mut self, bytes: &[u8]) {
+        let mut chunks = bytes.chunks_exact(8);
+        for chunk in &mut chunks {
+            self.add(u64::from_le_bytes(chunk.try_into().unwrap()));
+        }
+        let mut rest = chunks.remainder();
+        if rest.len() >= 4 {
+            self.add(u32::from_le_bytes(rest[..4].try_into().unwrap()).into());
+            rest = &rest[4..];
+        }
+        for &b in rest {
+            self.add(b.into());
+        }
+    }
+
+    #[inline]
+    fn write_u8(&mut self, i: u8) {
+        self.add(i.into());
+    }
+
+    #[inline]
+    fn write_u16(&mut self, i: u16) {
+        self.add(i.into());
+    }
+
+    #[inline]
+    fn write_u32(&mut self, i: u32) {
+        self.add(i.into());
+    }
+
+    #[inline]
+    fn write_u64(&mut self, i: u64) {
+        self.add(i);
+    }
+
+    #[inline]
+    fn write_usize(&mut self, i: usize) {
+        self.add(i as u64);
+    }
+
+    #[inline]
+    fn finish(&self) -> u64 {
+        self.hash
+    }
+}
+
+/// The starting state of the hashers of maps and sets created from now on.
+static MAP_SEED: AtomicU64 = AtomicU64::new(0);
+
+/// Makes maps and sets created from now on hash differently, and so iterate in a different
+/// order. Zero, the default, gives the stable order.
+pub fn set_map_seed(seed: u64) {
+    MAP_SEED.store(seed, Ordering::Relaxed);
+}
+
+/// Builds the hashers of [`FastMap`] and [`FastSet`], seeded when the map is created.
+#[derive(Clone, Copy, Debug)]
+pub struct FxBuildHasher {
+    seed: u64,
+}
+
+impl Default for FxBuildHasher {
+    fn default() -> Self {
+        Self {
+            seed: MAP_SEED.load(Ordering::Relaxed),
+        }
+    }
+}
+
+impl BuildHasher for FxBuildHasher {
+    type Hasher = FxHasher;
+
+    fn build_hasher(&self) -> FxHasher {
+        FxHasher { hash: self.seed }
+    }
+}
+
+/// A `HashMap` using [`FxHasher`]. Create it with `FastMap::default()`.
+pub type FastMap<K, V> = HashMap<K, V, FxBuildHasher>;
+
+/// A `HashSet` using [`FxHasher`]. Create it with `FastSet::default()`.
+pub type FastSet<T> = HashSet<T, FxBuildHasher>;
+
+/// Hashes a single value with [`FxHasher`].
+pub fn hash_one(value: &(impl Hash + ?Sized)) -> u64 {
+    let mut hasher = FxHasher::default();
+    value.hash(&mut hasher);
+    hasher.finish()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Instant;
+
+    use super::*;
+
+    #[test]
+    fn hash_stable() {
+        // Must never change, solvers may depend on the iteration order it gives
+        assert_eq!(hash_one(&0u64), 0);
+        assert_eq!(hash_one(&1u64), SEED);
+        assert_eq!(hash_one(&[1u8, 2, 3][..]), 0x63cd_0158_ba8b_c12d);
+        assert_eq!(hash_one("day05"), hash_one("day05"));
+        assert_ne!(hash_one("day05"), hash_one("day50"));
+
+        let mut map = FastMap::default();
+        map.insert("seed", 1);
+        map.insert("soil", 2);
+        assert_eq!(map["soil"], 2);
+    }
+
+    fn time_inserts<S: BuildHasher + Default>() -> std::time::Duration {
+        let start = Instant::now();
+        let mut map = HashMap::with_hasher(S::default());
+        for i in 0..1_000_000usize {
+            *map.entry((i % 1000, i / 1000)).or_insert(0usize) += i;
+        }
+        assert_eq!(map.len(), 1_000_000);
+        start.elapsed()
+    }
+
+    /// Compares against the std hasher on a memo-like workload. Run with
+    /// `cargo test --release hash_bench -- --ignored --nocapture`.
+    #[test]
+    #[ignore]
+    fn hash_bench() {
+        let sip = time_inserts::<std::collections::hash_map::RandomState>();
+        let fx = time_inserts::<FxBuildHasher>();
+        println!(
+            "1M inserts of (usize, usize): SipHash {sip:?}, FxHash {fx:?} ({:.1}x)",
+            sip.as_secs_f64() / fx.as_secs_f64()
+        );
+    }
+}
@@ -0,0 +1,271 @@
+//! A look at an input file before solving it: its shape, the characters in it, and whether the
+//! day's parser accepts it.
+
+use std::{cell::RefCell, collections::BTreeMap, fmt, panic, sync::Once};
+
+/// What a day expects its input to look like.
+#[derive(Clone, Copy, Debug)]
+pub struct Format {
+    /// Every character the input may contain, apart from newlines.
+    pub chars: &'static str,
+    /// Whether every section is a grid with rows of equal width.
+    pub grid: bool,
+}
+
+/// A block of lines without blank lines in between.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Section {
+    /// Line number of the first row, counting from 1.
+    pub first_line: usize,
+    pub rows: usize,
+    /// The width of the first row.
+    pub width: usize,
+    /// Line numbers of the rows that are not as wide as the first.
+    pub uneven: Vec<usize>,
+}
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Unexpected {
+    pub ch: char,
+    pub count: usize,
+    /// Line and column of the first occurrence, counting from 1.
+    pub first: (usize, usize),
+}
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Shape {
+    pub lines: usize,
+    pub blank_lines: usize,
+    pub sections: Vec<Section>,
+    /// Occurrences of every character, without newlines.
+    pub histogram: BTreeMap<char, usize>,
+    /// Characters the format doesn't allow, or control and non-ASCII characters if there is no
+    /// format.
+    pub unexpected: Vec<Unexpected>,
+    pub grid: bool,
+}
+
+impl Shape {
+    pub fn new(data: &str, format: Option<&Format>) -> Self {
+        let mut ret = Self {
+            lines: 0,
+            blank_lines: 0,
+            sections: Vec::new(),
+            histogram: BTreeMap::new(),
+            unexpected: Vec::new(),
+            grid: format.is_some_and(|v| v.grid),
+        };
+        let allowed = |ch: char| match format {
+            Some(format) => format.chars.contains(ch),
+            None => ch.is_ascii() && (!ch.is_control() || ch == '\t'),
+        };
+
+        let mut in_section = false;
+        let lines = (!data.is_empty()).then(|| data.strip_suffix('\n').unwrap_or(data).split('\n'));
+        for (i, line) in lines.into_iter().flatten().enumerate() {
+            let number = i + 1;
+            ret.lines += 1;
+            for (column, ch) in line.chars().enumerate() {
+                *ret.histogram.entry(ch).or_default() += 1;
+                if allowed(ch) {
+                    continue;
+                }
+                match ret.unexpected.iter_mut().find(|v| v.ch == ch) {
+                    Some(v) => v.count += 1,
+                    None => ret.unexpected.push(Unexpected {
+                        ch,
+                        count: 1,
+                        first: (number, column + 1),
+                    }),
+                }
+            }
+
+            let width = line.trim_end_matches('\r').chars().count();
+            if width == 0 {
+                ret.blank_lines += 1;
+                in_section = false;
+            } else if in_section {
+                let section = ret.sections.last_mut().unwrap();
+                section.rows += 1;
+                if width != section.width {
+                    section.uneven.push(number);
+                }
+            } else {
+                in_section = true;
+                ret.sections.push(Section {
+                    first_line: number,
+                    rows: 1,
+                    width,
+                    uneven: Vec::new(),
+                });
+            }
+        }
+        ret
+    }
+
+    /// Whether the shape fits the format: no unexpected characters, and even rows for grids.
+    pub fn ok(&self) -> bool {
+        self.unexpected.is_empty()
+            && !(self.grid && self.sections.iter().any(|v| !v.uneven.is_empty()))
+    }
+}
+
+impl fmt::Display for Shape {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let plural = |n: usize| if n == 1 { "" } else { "s" };
+        writeln!(
+            f,
+            "Lines: {} ({} blank), {} section{}",
+            self.lines,
+            self.blank_lines,
+            self.sections.len(),
+            plural(self.sections.len())
+        )?;
+        for (i, section) in self.sections.iter().enumerate() {
+            write!(
+                f,
+                "Section {} (line {}): {}x{}",
+                i + 1,
+                section.first_line,
+                section.width,
+                section.rows
+            )?;
+            if section.uneven.is_empty() {
+                writeln!(f, ", all rows equal width")?;
+            } else {
+                let lines: Vec<_> = section.uneven.iter().map(usize::to_string).collect();
+                // Only a problem for grids
+                let uneven = if self.grid { "UNEVEN" } else { "uneven" };
+                writeln!(
+                    f,
+                    ", {uneven} rows on line{} {}",
+                    plural(lines.len()),
+                    lines.join(", ")
+                )?;
+            }
+        }
+        let histogram: Vec<_> = self
+            .histogram
+            .iter()
+            .map(|(ch, count)| format!("{ch:?} {count}"))
+            .collect();
+        writeln!(f, "Characters: {}", histogram.join(", "))?;
+        if self.unexpected.is_empty() {
+            writeln!(f, "Unexpected characters: none")
+        } else {
+            let unexpected: Vec<_> = self
+                .unexpected
+                .iter()
+                .map(|v| {
+                    format!(
+                        "{:?} x{} (first on line {}, column {})",
+                        v.ch, v.count, v.first.0, v.first.1
+                    )
+                })
+                .collect();
+            writeln!(f, "Unexpected characters: {}", unexpected.join(", "))
+        }
+    }
+}
+
+thread_local! {
+    /// `Some` while `validate` runs on this thread, then holding where the parser panicked.
+    static CAPTURED: RefCell<Option<Option<String>>> = const { RefCell::new(None) };
+}
+
+/// Installs a panic hook, once for the whole process, that records panics on threads running
+/// `validate` instead of printing them. Panics anywhere else go to the previous hook.
+fn install_hook() {
+    static INSTALL: Once = Once::new();
+    INSTALL.call_once(|| {
+        let previous = panic::take_hook();
+        panic::set_hook(Box::new(move |info| {
+            let captured = CAPTURED.with(|v| match v.borrow_mut().as_mut() {
+                Some(location) => {
+                    *location = info.location().map(ToString::to_string);
+                    true
+                }
+                None => false,
+            });
+            if !captured {
+                previous(info);
+            }
+        }));
+    });
+}
+
+/// Runs a day's parser on `data`, returning where and why it gave up if it panics. The panic is
+/// not printed.
+pub fn validate(validate: crate::days::ValidateFn, data: &str) -> Result<(), String> {
+    install_hook();
+    CAPTURED.with(|v| *v.borrow_mut() = Some(None));
+    let res = panic::catch_unwind(|| validate(data));
+    let location = CAPTURED.with(|v| v.borrow_mut().take()).flatten();
+
+    res.map_err(|payload| {
+        let message = payload
+            .downcast_ref::<&str>()
+            .copied()
+            .or_else(|| payload.downcast_ref::<String>().map(String::as_str))
+            .unwrap_or("parser panicked");
+        match location {
+            Some(location) => format!("{message} (at {location})"),
+            None => message.to_owned(),
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const GRID: Format = Format {
+        chars: ".#",
+        grid: true,
+    };
+
+    #[test]
+    fn inspect_shape() {
+        let shape = Shape::new("#.#\n..#\n\n##\n#.\n#.\n", Some(&GRID));
+        assert_eq!(shape.lines, 6);
+        assert_eq!(shape.blank_lines, 1);
+        assert_eq!(shape.histogram[&'#'], 7);
+        assert!(shape.ok());
+
+        let shape = Shape::new("#.#\n..\n\n.x\r\nx.\r\n", Some(&GRID));
+        assert!(!shape.ok());
+        assert_eq!(
+            shape.to_string(),
+            "Lines: 5 (1 blank), 2 sections\n\
+             Section 1 (line 1): 3x2, UNEVEN rows on line 2\n\
+             Section 2 (line 4): 2x2, all rows equal width\n\
+             Characters: '\\r' 2, '#' 2, '.' 5, 'x' 2\n\
+             Unexpected characters: 'x' x2 (first on line 4, column 2), '\\r' x2 (first on line 4, column 3)\n"
+        );
+
+        // Without a format only odd characters are unexpected, and uneven rows are fine
+        let shape = Shape::new("abc\nde\u{e9}\n", None);
+        assert_eq!(shape.unexpected.len(), 1);
+        assert_eq!(shape.unexpected[0].first, (2, 3));
+        assert!(!shape.ok());
+        assert!(Shape::new("abc\nd\n", None).ok());
+    }
+
+    #[test]
+    fn inspect_validate() {
+        assert_eq!(validate(|data| assert!(!data.is_empty()), "x"), Ok(()));
+        let err = validate(|data| assert!(data.is_empty(), "not empty"), "x").unwrap_err();
+        assert!(err.starts_with("not empty (at src/inspect.rs:"), "{err}");
+
+        // Concurrent validations each see their own panic
+        std::thread::scope(|scope| {
+            let threads: Vec<_> = (0..4)
+                .map(|_| scope.spawn(|| validate(|data| assert!(data.is_empty()), "x")))
+                .collect();
+            for thread in threads {
+                let err = thread.join().unwrap().unwrap_err();
+                assert!(err.contains("(at src/inspect.rs:"), "{err}");
+            }
+        });
+    }
+}
@@ -0,0 +1,24 @@
+pub mod alloc;
+pub mod arith;
+pub mod bench;
+pub mod days;
+pub mod determinism;
+pub mod explain;
+pub mod graph;
+pub mod grid;
+pub mod hash;
+pub mod inspect;
+pub mod limits;
+pub mod log;
+pub mod math;
+pub mod memo;
+pub mod par;
+pub mod parse;
+pub mod range;
+pub mod repl;
+pub mod report;
+pub mod scale;
+#[cfg(test)]
+mod snapshot;
+pub mod vis;
+pub mod y2023;
@@ -0,0 +1,208 @@
+//! Running solvers with a time limit per step and a soft memory cap.
+//!
+//! The solver runs on a worker thread while the calling thread watches the clock and the
+//! [`alloc`](crate::alloc) statistics. Threads can't be killed, so a worker that exceeds a limit
+//! is abandoned and keeps running in the background until the process exits.
+
+use std::{
+    fmt,
+    sync::mpsc::{self, RecvTimeoutError},
+    time::{Duration, Instant},
+};
+
+use crate::{
+    alloc,
+    days::{Event, Solve, Step},
+};
+
+/// How often the limits are checked while waiting for the worker.
+const POLL_INTERVAL: Duration = Duration::from_millis(10);
+
+#[derive(Clone, Copy, Default, Debug)]
+pub struct Limits {
+    /// Time allowed for every step.
+    pub timeout: Option<Duration>,
+    /// Bytes the whole process may have allocated while the solver runs.
+    pub memory: Option<usize>,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Failure {
+    Timeout(Duration),
+    /// More than the cap was in use, with the amount in use when it was noticed.
+    Memory {
+        in_use: usize,
+        cap: usize,
+    },
+    Panic,
+}
+
+impl fmt::Display for Failure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Timeout(after) => write!(f, "TIMEOUT (after {after:?})"),
+            Self::Memory { in_use, cap } => write!(
+                f,
+                "MEMORY LIMIT ({} MiB in use, cap is {} MiB)",
+                in_use >> 20,
+                cap >> 20
+            ),
+            Self::Panic => write!(f, "PANIC"),
+        }
+    }
+}
+
+/// Runs `solve` on a worker thread and passes its events on to `on_event` as they arrive. Stops
+/// waiting when the worker is done or fails, and returns the step that failed.
+pub fn run(
+    solve: Solve,
+    data: &str,
+    limits: Limits,
+    mut on_event: impl FnMut(Event),
+) -> Result<(), (Step, Failure)> {
+    let (tx, rx) = mpsc::channel();
+    let data = data.to_owned();
+    let worker = std::thread::Builder::new()
+        .name("solver".to_owned())
+        .spawn(move || solve(&data, &mut |event| drop(tx.send(event))))
+        .expect("cannot spawn solver thread");
+
+    let mut step = Step::Parse;
+    let mut started = Instant::now();
+    loop {
+        let wait = match limits.timeout {
+            Some(timeout) => timeout.saturating_sub(started.elapsed()).min(POLL_INTERVAL),
+            None => POLL_INTERVAL,
+        };
+        let first = match rx.recv_timeout(wait) {
+            Ok(event) => Some(event),
+            Err(RecvTimeoutError::Timeout) => None,
+            Err(RecvTimeoutError::Disconnected) => {
+                // The default panic hook has already printed the message
+                return worker.join().map_err(|_| (step, Failure::Panic));
+            }
+        };
+        // Measured before catching up with the worker's events, so the step that pushed memory
+        // over the cap has always announced itself
+        let in_use = alloc::in_use();
+        for event in first.into_iter().chain(rx.try_iter()) {
+            if let Event::Started(next) = event {
+                step = next;
+                started = Instant::now();
+            }
+            on_event(event);
+        }
+
+        if let Some(timeout) = limits.timeout {
+            if started.elapsed() >= timeout {
+                return Err((step, Failure::Timeout(timeout)));
+            }
+        }
+        if let Some(cap) = limits.memory {
+            if in_use > cap {
+                return Err((step, Failure::Memory { in_use, cap }));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Barrier, Mutex};
+
+    use super::*;
+
+    /// Where the `slow` and `hungry` workers wait for their test to be done with them, once to
+    /// be released and once more after freeing their memory.
+    static HOLD: Barrier = Barrier::new(2);
+
+    fn fake_day(data: &str, report: &mut dyn FnMut(Event)) {
+        crate::days::unparsed(data, report, str::len, |_| {
+            if data == "slow" {
+                HOLD.wait();
+                HOLD.wait();
+            } else if data == "hungry" {
+                let v: Vec<u8> = Vec::with_capacity(1 << 30);
+                HOLD.wait();
+                drop(v);
+                HOLD.wait();
+            } else if data == "panic" {
+                panic!("test panic");
+            }
+            42
+        });
+    }
+
+    fn run_answers(data: &str, limits: Limits) -> (Vec<String>, Result<(), (Step, Failure)>) {
+        let mut answers = Vec::new();
+        let res = run(fake_day, data, limits, |event| {
+            if let Event::Finished {
+                answer: Some(answer),
+                ..
+            } = event
+            {
+                answers.push(answer);
+            }
+        });
+        (answers, res)
+    }
+
+    /// Like [`run_answers`] for a worker that blocks on [`HOLD`], which gets released again
+    /// before returning so it doesn't outlive the test.
+    fn run_held(data: &str, limits: Limits) -> (Vec<String>, Result<(), (Step, Failure)>) {
+        static LOCK: Mutex<()> = Mutex::new(());
+        let _lock = LOCK.lock().unwrap_or_else(|e| e.into_inner());
+        let ret = run_answers(data, limits);
+        HOLD.wait();
+        HOLD.wait();
+        ret
+    }
+
+    #[test]
+    fn limits_ok() {
+        let limits = Limits {
+            timeout: Some(Duration::from_secs(10)),
+            memory: None,
+        };
+        assert_eq!(
+            run_answers("abc", limits),
+            (vec!["3".into(), "42".into()], Ok(()))
+        );
+    }
+
+    #[test]
+    fn limits_timeout() {
+        let limits = Limits {
+            timeout: Some(Duration::from_millis(50)),
+            memory: None,
+        };
+        let (answers, res) = run_held("slow", limits);
+        assert_eq!(answers, ["4"]);
+        assert_eq!(
+            res,
+            Err((Step::Part(2), Failure::Timeout(Duration::from_millis(50))))
+        );
+    }
+
+    #[test]
+    fn limits_memory() {
+        let cap = alloc::in_use() + (256 << 20);
+        let limits = Limits {
+            timeout: None,
+            memory: Some(cap),
+        };
+        let (answers, res) = run_held("hungry", limits);
+        assert_eq!(answers, ["6"]);
+        assert!(
+            matches!(res, Err((Step::Part(2), Failure::Memory { cap: c, .. })) if c == cap),
+            "{res:?}"
+        );
+    }
+
+    #[test]
+    fn limits_panic() {
+        let (answers, res) = run_answers("panic", Limits::default());
+        assert_eq!(answers, ["5"]);
+        assert_eq!(res, Err((Step::Part(2), Failure::Panic)));
+    }
+}
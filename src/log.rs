@@ -0,0 +1,86 @@
+//! Minimal leveled logging to stderr for looking inside the solvers.
+//!
+//! Nothing is printed by default. The level is raised with `-v`/`-vv` on the command line or with
+//! the `AOC_LOG` environment variable (`debug`, `trace` or a number).
+
+use std::sync::atomic::{AtomicU8, Ordering};
+
+pub const ENV_VAR: &str = "AOC_LOG";
+
+static LEVEL: AtomicU8 = AtomicU8::new(0);
+
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Debug)]
+#[repr(u8)]
+pub enum Level {
+    Debug = 1,
+    Trace = 2,
+}
+
+impl Level {
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::Debug => "DEBUG",
+            Self::Trace => "TRACE",
+        }
+    }
+}
+
+fn parse_env(value: &str) -> u8 {
+    match value.trim().to_ascii_lowercase().as_str() {
+        "debug" => Level::Debug as u8,
+        "trace" => Level::Trace as u8,
+        v => v.parse().unwrap_or(0),
+    }
+}
+
+/// Sets the level to `verbosity` (the number of `-v` flags) or to the level requested by
+/// `AOC_LOG`, whichever is higher.
+pub fn init(verbosity: u8) {
+    let env = std::env::var(ENV_VAR).map_or(0, |v| parse_env(&v));
+    set_verbosity(verbosity.max(env));
+}
+
+pub fn set_verbosity(verbosity: u8) {
+    LEVEL.store(verbosity, Ordering::Relaxed);
+}
+
+pub fn enabled(level: Level) -> bool {
+    LEVEL.load(Ordering::Relaxed) >= level as u8
+}
+
+#[doc(hidden)]
+pub fn write(level: Level, module: &str, args: std::fmt::Arguments) {
+    let module = module.rsplit("::").next().unwrap_or(module);
+    eprintln!("[{} {module}] {args}", level.as_str());
+}
+
+#[macro_export]
+macro_rules! debug {
+    ($($arg:tt)*) => {
+        if $crate::log::enabled($crate::log::Level::Debug) {
+            $crate::log::write($crate::log::Level::Debug, module_path!(), format_args!($($arg)*));
+        }
+    };
+}
+
+#[macro_export]
+macro_rules! trace {
+    ($($arg:tt)*) => {
+        if $crate::log::enabled($crate::log::Level::Trace) {
+            $crate::log::write($crate::log::Level::Trace, module_path!(), format_args!($($arg)*));
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn log_parse_env() {
+        assert_eq!(parse_env("debug"), 1);
+        assert_eq!(parse_env("TRACE"), 2);
+        assert_eq!(parse_env("2"), 2);
+        assert_eq!(parse_env("nonsense"), 0);
+    }
+}
@@ -0,0 +1,611 @@
+use std::{process::ExitCode, time::Duration};
+
+use aoc23::{
+    alloc,
+    bench::{self, Record},
+    days::{self, Day, Event, Step},
+    determinism,
+    explain::Table,
+    inspect::{self, Shape},
+    limits::{self, Failure, Limits},
+    log, par,
+    report::{self, Row},
+    scale::{self, Rng},
+    vis,
+};
+
+const USAGE: &str = "\
+usage: aoc23 [-v|-vv] [run] [YEAR] [DAY...] [--visualize] [--delay MS] [--frames FILE]
+             [--timeout SECONDS] [--memory-limit MIB] [--threads N]
+             [--explain [--json]]
+       aoc23 [-v|-vv] bench [--check] [--threshold PERCENT] [--runs N] [--threads N]
+       aoc23 replay FILE [--delay MS]
+       aoc23 repl [YEAR] DAY
+       aoc23 inspect [YEAR] DAY [FILE]
+       aoc23 determinism [YEAR] [DAY...] [--runs N]
+       aoc23 scale [YEAR] DAY [--start SIZE] [--steps N] [--runs N] [--timeout SECONDS]
+       aoc23 report [YEAR] [DAY...] [--output DIR] [--timeout SECONDS]
+
+Days given without a year are from the latest year. Inputs are read from
+input/YEAR/dayNN.txt. 2023 inputs in the old input/dayNN.txt location are still
+found, but should be moved to input/2023/.
+
+Every step of a run gets 60 seconds unless --timeout says otherwise, 0 disables
+the limit. A run that goes over --memory-limit stops the runner.
+
+--explain shows how the days that support it got every answer, line by line,
+as a table or with --json as one JSON object per part.
+
+Days that spread their lines over threads use one per core unless --threads
+says otherwise. The benchmark also times them on a single thread to show the
+speedup.
+
+inspect describes the input file, by default the day's input, and checks it
+against what the day expects without solving anything.
+
+determinism runs every day --runs (8) times with different hash map seeds and
+thread counts, and fails if any answer or explanation changes.
+
+scale times the day on generated inputs, doubling the size from --start (16)
+for --steps (6) steps, and fits how the time grows with the input length. It
+stops early when a step runs over --timeout (10 seconds).
+
+The report is written to DIR/report.md and DIR/report.html, by default in
+report/. Answers are verified against input/YEAR/dayNN.answers, which has the
+answer of every part on its own line. Blank lines are skipped, and a file with
+more than two answers stops the report.
+
+Verbosity can also be set with AOC_LOG=debug|trace.";
+
+#[global_allocator]
+static GLOBAL: alloc::Counting = alloc::Counting;
+
+fn load_input(day: &Day) -> Option<String> {
+    let path = day.input_path();
+    match std::fs::read_to_string(&path) {
+        Ok(data) => Some(data),
+        Err(e) => {
+            println!("{day}: skipped, cannot read {path}: {e}");
+            None
+        }
+    }
+}
+
+/// Resolves `[YEAR] [DAY...]`. Nothing selects every day of every year.
+fn select_days(args: &[String]) -> Option<Vec<&'static Day>> {
+    let mut year = None;
+    let mut ret = Vec::new();
+    for arg in args {
+        let num: u16 = arg.parse().ok()?;
+        if num >= 1000 {
+            year = Some(num);
+        } else {
+            let year = year.unwrap_or_else(days::latest_year);
+            ret.push(days::find(year, num.try_into().ok()?)?);
+        }
+    }
+    if ret.is_empty() {
+        match year {
+            Some(year) => ret.extend(days::year(year)),
+            None => ret.extend(days::all()),
+        }
+    }
+    (!ret.is_empty()).then_some(ret)
+}
+
+fn run(mut args: impl Iterator<Item = String>) -> ExitCode {
+    let mut selection = Vec::new();
+    let mut vis_config = vis::Config {
+        terminal: false,
+        delay: Duration::from_millis(50),
+        frames: None,
+    };
+    let mut limits = Limits {
+        timeout: Some(Duration::from_secs(60)),
+        memory: None,
+    };
+    let mut explain = false;
+    let mut json = false;
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--visualize" => vis_config.terminal = true,
+            "--explain" => explain = true,
+            "--json" => json = true,
+            "--timeout" => match args.next().and_then(|v| v.parse().ok()) {
+                Some(0) => limits.timeout = None,
+                Some(v) => limits.timeout = Some(Duration::from_secs(v)),
+                None => return usage(),
+            },
+            "--memory-limit" => match args.next().and_then(|v| v.parse::<usize>().ok()) {
+                Some(v) => limits.memory = Some(v << 20),
+                None => return usage(),
+            },
+            "--threads" => match args.next().and_then(|v| v.parse().ok()) {
+                Some(v) => par::set_threads(v),
+                None => return usage(),
+            },
+            "--delay" => match args.next().and_then(|v| v.parse().ok()) {
+                Some(v) => vis_config.delay = Duration::from_millis(v),
+                None => return usage(),
+            },
+            "--frames" => match args.next().map(std::fs::File::create) {
+                Some(Ok(file)) => vis_config.frames = Some(file),
+                Some(Err(e)) => {
+                    eprintln!("cannot create frames file: {e}");
+                    return ExitCode::FAILURE;
+                }
+                None => return usage(),
+            },
+            _ => selection.push(arg),
+        }
+    }
+    let Some(days) = select_days(&selection) else {
+        return usage();
+    };
+    if json && !explain {
+        return usage();
+    }
+    vis::init(vis_config);
+
+    let mut status = ExitCode::SUCCESS;
+    for day in days {
+        let Some(data) = load_input(day) else {
+            continue;
+        };
+        println!("{day}");
+        let res = limits::run(day.solve, &data, limits, |event| match event {
+            Event::Finished {
+                step: Step::Parse,
+                elapsed,
+                ..
+            } => println!("Parsed in {elapsed:?}"),
+            Event::Finished {
+                step: Step::Part(part),
+                answer,
+                elapsed,
+                ..
+            } => {
+                println!("Part {part}: {} ({elapsed:?})", answer.unwrap_or_default());
+            }
+            Event::Started(_) => (),
+        });
+        if let Err((step, failure)) = res {
+            match step {
+                Step::Parse => println!("Parse: {failure}"),
+                Step::Part(part) => println!("Part {part}: {failure}"),
+            }
+            status = ExitCode::FAILURE;
+            // The abandoned solver keeps its memory, and most likely keeps asking for more
+            if matches!(failure, Failure::Memory { .. }) {
+                break;
+            }
+        } else if explain {
+            print_explanation(day, &data, json);
+        }
+    }
+
+    if let Err(e) = vis::finish() {
+        eprintln!("cannot write frames file: {e}");
+        return ExitCode::FAILURE;
+    }
+    status
+}
+
+fn print_explanation(day: &Day, data: &str, json: bool) {
+    let Some(explain) = day.explain else {
+        println!("{day} has no explanation");
+        return;
+    };
+    for part in 1..=2 {
+        let table = explain(data, part);
+        if json {
+            println!(
+                r#"{{"year": {}, "day": {}, "part": {part}, "rows": {}}}"#,
+                day.year,
+                day.day,
+                table.json()
+            );
+        } else {
+            println!("Part {part} explained:\n{}", table.text());
+        }
+    }
+}
+
+fn replay(mut args: impl Iterator<Item = String>) -> ExitCode {
+    let Some(path) = args.next() else {
+        return usage();
+    };
+    let mut delay = Duration::from_millis(50);
+    while let Some(arg) = args.next() {
+        match (arg.as_str(), args.next().and_then(|v| v.parse().ok())) {
+            ("--delay", Some(v)) => delay = Duration::from_millis(v),
+            _ => return usage(),
+        }
+    }
+    match vis::replay(&path, delay) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("cannot replay {path}: {e}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn repl(args: impl Iterator<Item = String>) -> ExitCode {
+    let selection: Vec<_> = args.collect();
+    let day = match select_days(&selection).as_deref() {
+        Some(&[day]) if !selection.is_empty() => day,
+        _ => return usage(),
+    };
+    let Some(repl) = day.repl else {
+        eprintln!("{day} has no REPL commands");
+        return ExitCode::FAILURE;
+    };
+    let Some(data) = load_input(day) else {
+        return ExitCode::FAILURE;
+    };
+    let session = repl(&data);
+    println!("{day}, type `help` for a list of commands");
+    match aoc23::repl::run(
+        session.as_ref(),
+        std::io::stdin().lock(),
+        std::io::stdout(),
+        &format!("{day}> "),
+    ) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("{e}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn inspect(args: impl Iterator<Item = String>) -> ExitCode {
+    let (files, selection): (Vec<_>, Vec<_>) = args.partition(|v| v.parse::<u16>().is_err());
+    let day = match (select_days(&selection).as_deref(), &files[..]) {
+        (Some(&[day]), [] | [_]) if !selection.is_empty() => day,
+        _ => return usage(),
+    };
+    let path = files.first().cloned().unwrap_or_else(|| day.input_path());
+    let data = match std::fs::read_to_string(&path) {
+        Ok(v) => v,
+        Err(e) => {
+            eprintln!("cannot read {path}: {e}");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    println!("{day}, {path}");
+    let shape = Shape::new(&data, day.format.as_ref());
+    print!("{shape}");
+    let valid = match day.validate.map(|v| inspect::validate(v, &data)) {
+        Some(Ok(())) => {
+            println!("Format: accepted by the parser");
+            true
+        }
+        Some(Err(e)) => {
+            println!("Format: REJECTED by the parser: {e}");
+            false
+        }
+        None => {
+            println!("Format: not checked, {day} has no parser to check with");
+            true
+        }
+    };
+    if shape.ok() && valid {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::FAILURE
+    }
+}
+
+fn determinism(mut args: impl Iterator<Item = String>) -> ExitCode {
+    let mut selection = Vec::new();
+    let mut runs = 8;
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--runs" => match args.next().and_then(|v| v.parse().ok()) {
+                Some(v) => runs = v,
+                None => return usage(),
+            },
+            _ => selection.push(arg),
+        }
+    }
+    let Some(days) = select_days(&selection) else {
+        return usage();
+    };
+
+    let mut status = ExitCode::SUCCESS;
+    for day in days {
+        let Some(data) = load_input(day) else {
+            continue;
+        };
+        match determinism::check(day.solve, day.explain, &data, runs) {
+            Ok(()) => println!("{day}: same output in {runs} runs"),
+            Err(mismatch) => {
+                println!("{day}: NONDETERMINISTIC, {mismatch}");
+                status = ExitCode::FAILURE;
+            }
+        }
+    }
+    status
+}
+
+fn scale(mut args: impl Iterator<Item = String>) -> ExitCode {
+    let mut selection = Vec::new();
+    let mut start = 16;
+    let mut steps = 6;
+    let mut runs = 3;
+    let mut limits = Limits {
+        timeout: Some(Duration::from_secs(10)),
+        memory: None,
+    };
+    while let Some(arg) = args.next() {
+        let value = match arg.as_str() {
+            "--start" => &mut start,
+            "--steps" => &mut steps,
+            "--runs" => &mut runs,
+            "--timeout" => match args.next().and_then(|v| v.parse().ok()) {
+                Some(0) => {
+                    limits.timeout = None;
+                    continue;
+                }
+                Some(v) => {
+                    limits.timeout = Some(Duration::from_secs(v));
+                    continue;
+                }
+                None => return usage(),
+            },
+            _ => {
+                selection.push(arg);
+                continue;
+            }
+        };
+        match args.next().and_then(|v| v.parse().ok()) {
+            Some(v) => *value = v,
+            None => return usage(),
+        }
+    }
+    let day = match select_days(&selection).as_deref() {
+        Some(&[day]) if !selection.is_empty() => day,
+        _ => return usage(),
+    };
+    let Some(generate) = day.generate else {
+        eprintln!("{day} has no input generator");
+        return ExitCode::FAILURE;
+    };
+
+    println!("{day}");
+    let mut table = Table::new(&["size", "bytes", "parse", "part 1", "part 2"]);
+    let mut points = Vec::new();
+    let mut status = ExitCode::SUCCESS;
+    for size in (0..steps).map(|i| start << i) {
+        let data = generate(size, &mut Rng::new(size as u64));
+        match scale::measure(day.solve, size, &data, runs, limits) {
+            Ok(point) => {
+                let time = |v: Option<Duration>| v.map_or("-".to_owned(), |v| format!("{v:?}"));
+                table.row([
+                    size.into(),
+                    point.bytes.into(),
+                    time(point.parse).into(),
+                    time(point.parts[0]).into(),
+                    time(point.parts[1]).into(),
+                ]);
+                points.push(point);
+            }
+            Err((step, failure)) => {
+                println!("Size {size}, {step}: {failure}");
+                status = ExitCode::FAILURE;
+                break;
+            }
+        }
+    }
+    print!("{}", table.text());
+
+    let growth: Vec<_> = [Step::Parse, Step::Part(1), Step::Part(2)]
+        .into_iter()
+        .filter_map(|step| Some(format!("{step} ~ n^{:.2}", scale::growth(&points, step)?)))
+        .collect();
+    if !growth.is_empty() {
+        println!("Growth with input length n: {}", growth.join(", "));
+    }
+    status
+}
+
+fn report(mut args: impl Iterator<Item = String>) -> ExitCode {
+    let mut selection = Vec::new();
+    let mut output = std::path::PathBuf::from("report");
+    let mut limits = Limits {
+        timeout: Some(Duration::from_secs(60)),
+        memory: None,
+    };
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--output" => match args.next() {
+                Some(v) => output = v.into(),
+                None => return usage(),
+            },
+            "--timeout" => match args.next().and_then(|v| v.parse().ok()) {
+                Some(0) => limits.timeout = None,
+                Some(v) => limits.timeout = Some(Duration::from_secs(v)),
+                None => return usage(),
+            },
+            _ => selection.push(arg),
+        }
+    }
+    let Some(days) = select_days(&selection) else {
+        return usage();
+    };
+
+    let mut rows = Vec::new();
+    for day in days {
+        let data = std::fs::read_to_string(day.input_path()).ok();
+        let mut row = Row::new(day, data.is_some());
+        row.expected = match report::expected_answers(day) {
+            Ok(expected) => expected,
+            Err(e) => {
+                eprintln!("{e}");
+                return ExitCode::FAILURE;
+            }
+        };
+        if let Some(data) = data {
+            row.failure = limits::run(day.solve, &data, limits, |event| row.record(event)).err();
+        }
+        println!("{day}: {}", row.status());
+        rows.push(row);
+    }
+
+    let commit = bench::current_commit();
+    let files = [
+        ("report.md", report::markdown(&rows, &commit)),
+        ("report.html", report::html(&rows, &commit)),
+    ];
+    for (name, contents) in files {
+        let path = output.join(name);
+        let res = std::fs::create_dir_all(&output).and_then(|()| std::fs::write(&path, contents));
+        if let Err(e) = res {
+            eprintln!("cannot write {}: {e}", path.display());
+            return ExitCode::FAILURE;
+        }
+        println!("Wrote {}", path.display());
+    }
+    ExitCode::SUCCESS
+}
+
+fn bench(mut args: impl Iterator<Item = String>) -> ExitCode {
+    let mut check = false;
+    let mut threshold = 10.0;
+    let mut runs = 5;
+    let mut threads = 0;
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--check" => check = true,
+            "--threshold" => match args.next().and_then(|v| v.parse().ok()) {
+                Some(v) => threshold = v,
+                None => return usage(),
+            },
+            "--runs" => match args.next().and_then(|v| v.parse().ok()) {
+                Some(v) => runs = v,
+                None => return usage(),
+            },
+            "--threads" => match args.next().and_then(|v| v.parse().ok()) {
+                Some(v) => threads = v,
+                None => return usage(),
+            },
+            _ => return usage(),
+        }
+    }
+
+    let commit = bench::current_commit();
+    let mut current = Vec::new();
+    par::set_threads(threads);
+    for day in days::all() {
+        let Some(data) = load_input(day) else {
+            continue;
+        };
+        let steps = step_times(&bench::measure(day.solve, &data, runs));
+        let single = (day.parallel && par::threads() > 1).then(|| {
+            par::set_threads(1);
+            let ret = step_times(&bench::measure(day.solve, &data, runs));
+            par::set_threads(threads);
+            ret
+        });
+        for (i, &(part, elapsed)) in steps.iter().enumerate() {
+            match single.as_ref().map(|v| v[i].1) {
+                Some(single) => println!(
+                    "{day} {}: {elapsed:?} ({:.1}x speedup over {single:?} on 1 thread)",
+                    step_name(part),
+                    single.as_secs_f64() / elapsed.as_secs_f64()
+                ),
+                None => println!("{day} {}: {elapsed:?}", step_name(part)),
+            }
+            current.push(Record::new(&commit, day.year, day.day, part, elapsed));
+        }
+    }
+
+    let history = match bench::load_history(bench::HISTORY_FILE) {
+        Ok(v) => v,
+        Err(e) => {
+            eprintln!("cannot read {}: {e}", bench::HISTORY_FILE);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    if check {
+        let regressions = bench::find_regressions(&history, &current, threshold);
+        for reg in &regressions {
+            println!(
+                "REGRESSION {} day {} {}: {:?} -> {:?} (+{:.1}%)",
+                reg.year,
+                reg.day,
+                step_name(reg.part),
+                reg.baseline,
+                reg.current,
+                reg.percent
+            );
+        }
+        if !regressions.is_empty() {
+            return ExitCode::FAILURE;
+        }
+        println!("No regressions above {threshold}%");
+    } else if let Err(e) = bench::append_history(bench::HISTORY_FILE, &current) {
+        eprintln!("cannot write {}: {e}", bench::HISTORY_FILE);
+        return ExitCode::FAILURE;
+    }
+
+    ExitCode::SUCCESS
+}
+
+/// The time of every step, with parts numbered as in [`Record::part`].
+fn step_times(solved: &days::Solved) -> Vec<(u8, Duration)> {
+    let parse = solved.parse.map(|elapsed| (bench::PARSE, elapsed));
+    let parts = (1..)
+        .zip(&solved.parts)
+        .map(|(part, (_, elapsed))| (part, *elapsed));
+    parse.into_iter().chain(parts).collect()
+}
+
+fn step_name(part: u8) -> String {
+    if part == bench::PARSE {
+        "parse".to_owned()
+    } else {
+        format!("part {part}")
+    }
+}
+
+fn usage() -> ExitCode {
+    eprintln!("{USAGE}");
+    ExitCode::FAILURE
+}
+
+fn main() -> ExitCode {
+    let mut verbosity = 0;
+    let args: Vec<_> = std::env::args()
+        .skip(1)
+        .filter(|arg| match arg.as_str() {
+            "-v" | "--verbose" => {
+                verbosity += 1;
+                false
+            }
+            "-vv" => {
+                verbosity += 2;
+                false
+            }
+            _ => true,
+        })
+        .collect();
+    log::init(verbosity);
+
+    let mut args = args.into_iter().peekable();
+    match args.peek().map(String::as_str) {
+        Some("run") => run(args.skip(1)),
+        Some("bench") => bench(args.skip(1)),
+        Some("replay") => replay(args.skip(1)),
+        Some("repl") => repl(args.skip(1)),
+        Some("inspect") => inspect(args.skip(1)),
+        Some("determinism") => determinism(args.skip(1)),
+        Some("scale") => scale(args.skip(1)),
+        Some("report") => report(args.skip(1)),
+        _ => run(args),
+    }
+}
@@ -0,0 +1,239 @@
+//! Number theory helpers. Everything that can overflow reports it instead of wrapping.
+
+/// The unsigned integer types the helpers below work on.
+pub trait Unsigned: Copy + Eq + Ord + std::fmt::Debug {
+    const ZERO: Self;
+    const ONE: Self;
+
+    fn rem(self, rhs: Self) -> Self;
+    fn div(self, rhs: Self) -> Self;
+    fn checked_mul(self, rhs: Self) -> Option<Self>;
+}
+
+macro_rules! impl_unsigned {
+    ($($ty:ty),*) => {
+        $(
+            impl Unsigned for $ty {
+                const ZERO: Self = 0;
+                const ONE: Self = 1;
+
+                fn rem(self, rhs: Self) -> Self {
+                    self % rhs
+                }
+
+                fn div(self, rhs: Self) -> Self {
+                    self / rhs
+                }
+
+                fn checked_mul(self, rhs: Self) -> Option<Self> {
+                    <$ty>::checked_mul(self, rhs)
+                }
+            }
+        )*
+    };
+}
+
+impl_unsigned!(u8, u16, u32, u64, u128, usize);
+
+pub fn gcd<T: Unsigned>(mut a: T, mut b: T) -> T {
+    while b != T::ZERO {
+        let rem = a.rem(b);
+        a = std::mem::replace(&mut b, rem);
+    }
+    a
+}
+
+/// Least common multiple of `a` and `b`, or `None` if it doesn't fit in `T`.
+pub fn checked_lcm<T: Unsigned>(a: T, b: T) -> Option<T> {
+    if a == T::ZERO || b == T::ZERO {
+        return Some(T::ZERO);
+    }
+    a.div(gcd(a, b)).checked_mul(b)
+}
+
+/// Greatest common divisor of all values, zero for an empty slice.
+pub fn gcd_all<T: Unsigned>(values: &[T]) -> T {
+    values.iter().fold(T::ZERO, |acc, &v| gcd(acc, v))
+}
+
+/// Least common multiple of all values, one for an empty slice, or `None` if it doesn't fit in
+/// `T`.
+pub fn checked_lcm_all<T: Unsigned>(values: &[T]) -> Option<T> {
+    values
+        .iter()
+        .try_fold(T::ONE, |acc, &v| checked_lcm(acc, v))
+}
+
+/// Extended Euclid: returns `(g, x, y)` with `g = gcd(a, b) >= 0` and `a * x + b * y = g`.
+pub fn extended_gcd(a: i128, b: i128) -> (i128, i128, i128) {
+    let (mut old_r, mut r) = (a, b);
+    let (mut old_s, mut s) = (1, 0);
+    let (mut old_t, mut t) = (0, 1);
+    while r != 0 {
+        let q = old_r / r;
+        (old_r, r) = (r, old_r - q * r);
+        (old_s, s) = (s, old_s - q * s);
+        (old_t, t) = (t, old_t - q * t);
+    }
+    if old_r < 0 {
+        (-old_r, -old_s, -old_t)
+    } else {
+        (old_r, old_s, old_t)
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum CrtError {
+    /// One of the moduli is zero.
+    ZeroModulus,
+    /// The congruences contradict each other.
+    NoSolution,
+    /// The combined modulus doesn't fit in a `u64`.
+    Overflow,
+}
+
+/// Solves the system `x = residue (mod modulus)` for every `(residue, modulus)` pair. The moduli
+/// don't need to be coprime. Returns the smallest non-negative solution and the modulus of the
+/// combined congruence, which is the LCM of all moduli.
+pub fn crt(congruences: &[(u64, u64)]) -> Result<(u64, u64), CrtError> {
+    let mut x: i128 = 0;
+    let mut m: i128 = 1;
+    for &(residue, modulus) in congruences {
+        if modulus == 0 {
+            return Err(CrtError::ZeroModulus);
+        }
+        let (r, n) = (i128::from(residue % modulus), i128::from(modulus));
+        let (g, p, _) = extended_gcd(m, n);
+        if (r - x) % g != 0 {
+            return Err(CrtError::NoSolution);
+        }
+        let lcm = match (m / g).checked_mul(n) {
+            Some(v) if v <= i128::from(u64::MAX) => v,
+            _ => return Err(CrtError::Overflow),
+        };
+        // x + m * k = r (mod n), with k = (r - x) / g * p (mod n / g). Both factors are below
+        // 2^64, so their product fits in a u128, and m * k < lcm fits in a u64.
+        let step = n / g;
+        let k = ((r - x) / g).rem_euclid(step) as u128 * p.rem_euclid(step) as u128;
+        let k = (k % step as u128) as i128;
+        x = (x + m * k).rem_euclid(lcm);
+        m = lcm;
+    }
+    Ok((x as u64, m as u64))
+}
+
+macro_rules! impl_isqrt {
+    ($name:ident, $ty:ty) => {
+        /// The largest integer whose square is at most `n`.
+        pub fn $name(n: $ty) -> $ty {
+            if n < 2 {
+                return n;
+            }
+            // Start above the root and use Newton's method, which decreases monotonically from
+            // there
+            let shift = (<$ty>::BITS - n.leading_zeros()).div_ceil(2);
+            let mut x: $ty = 1 << shift;
+            loop {
+                let next = (x + n / x) / 2;
+                if next >= x {
+                    return x;
+                }
+                x = next;
+            }
+        }
+    };
+}
+
+impl_isqrt!(isqrt_u64, u64);
+impl_isqrt!(isqrt_u128, u128);
+
+/// `base.pow(exp) % modulus`, or `None` for a zero modulus.
+pub fn mod_pow(base: u64, mut exp: u64, modulus: u64) -> Option<u64> {
+    if modulus == 0 {
+        return None;
+    }
+    let modulus = u128::from(modulus);
+    let mut base = u128::from(base) % modulus;
+    let mut ret = 1 % modulus;
+    while exp > 0 {
+        if exp & 1 == 1 {
+            ret = ret * base % modulus;
+        }
+        base = base * base % modulus;
+        exp >>= 1;
+    }
+    Some(ret as u64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn math_gcd_lcm() {
+        assert_eq!(gcd(12u64, 18), 6);
+        assert_eq!(gcd(0u64, 7), 7);
+        assert_eq!(gcd_all::<u32>(&[]), 0);
+        assert_eq!(gcd_all(&[12u32, 18, 8]), 2);
+        assert_eq!(checked_lcm(4u64, 6), Some(12));
+        assert_eq!(checked_lcm(0u64, 6), Some(0));
+        assert_eq!(checked_lcm_all::<usize>(&[]), Some(1));
+        assert_eq!(checked_lcm_all(&[2usize, 3, 4, 5]), Some(60));
+    }
+
+    #[test]
+    fn math_lcm_overflow() {
+        // The naive a * b / gcd overflows here even though the result fits
+        assert_eq!(checked_lcm(u64::MAX, u64::MAX), Some(u64::MAX));
+        assert_eq!(checked_lcm(u64::MAX, 2), None);
+        assert_eq!(checked_lcm_all(&[1u8 << 7, 3]), None);
+    }
+
+    #[test]
+    fn math_extended_gcd() {
+        for (a, b) in [(240, 46), (46, 240), (-240, 46), (0, 5), (7, 0), (17, 5)] {
+            let (g, x, y) = extended_gcd(a, b);
+            assert_eq!(g, gcd(a.unsigned_abs(), b.unsigned_abs()) as i128);
+            assert_eq!(a * x + b * y, g);
+        }
+    }
+
+    #[test]
+    fn math_crt() {
+        assert_eq!(crt(&[(2, 3), (3, 5), (2, 7)]), Ok((23, 105)));
+        // Not coprime, but consistent
+        assert_eq!(crt(&[(3, 4), (5, 6)]), Ok((11, 12)));
+        assert_eq!(crt(&[(1, 4), (2, 6)]), Err(CrtError::NoSolution));
+        assert_eq!(crt(&[(1, 0)]), Err(CrtError::ZeroModulus));
+        assert_eq!(crt(&[]), Ok((0, 1)));
+        assert_eq!(
+            crt(&[(1, u64::MAX), (0, u64::MAX - 1)]),
+            Err(CrtError::Overflow)
+        );
+
+        let big = (1 << 30) + 15;
+        let (x, m) = crt(&[(5, big), (7, big + 2)]).unwrap();
+        assert_eq!(m, big * (big + 2));
+        assert_eq!((x % big, x % (big + 2)), (5, 7));
+    }
+
+    #[test]
+    fn math_isqrt() {
+        for n in 0..10_000u64 {
+            let r = isqrt_u64(n);
+            assert!(r * r <= n && (r + 1) * (r + 1) > n, "{n}");
+        }
+        assert_eq!(isqrt_u64(u64::MAX), u64::from(u32::MAX));
+        assert_eq!(isqrt_u128(u128::MAX), u128::from(u64::MAX));
+        assert_eq!(isqrt_u128(1 << 100), 1 << 50);
+        assert_eq!(isqrt_u128((1 << 100) - 1), (1 << 50) - 1);
+    }
+
+    #[test]
+    fn math_mod_pow() {
+        assert_eq!(mod_pow(2, 10, 1000), Some(24));
+        assert_eq!(mod_pow(3, 0, 1), Some(0));
+        assert_eq!(mod_pow(u64::MAX, u64::MAX, u64::MAX - 1), Some(1));
+        assert_eq!(mod_pow(5, 5, 0), None);
+    }
+}
@@ -0,0 +1,132 @@
+//! A cache for recursive solvers, with statistics on how well it works.
+
+use std::{fmt, fmt::Debug, hash::Hash};
+
+use crate::{hash::FastMap, trace};
+
+#[derive(Clone, Copy, PartialEq, Eq, Default, Debug)]
+pub struct Stats {
+    pub hits: usize,
+    pub misses: usize,
+    /// Entries dropped because the cache was full.
+    pub evictions: usize,
+    pub entries: usize,
+}
+
+impl fmt::Display for Stats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} hits, {} misses, {} entries",
+            self.hits, self.misses, self.entries
+        )?;
+        if self.evictions > 0 {
+            write!(f, ", {} evicted", self.evictions)?;
+        }
+        Ok(())
+    }
+}
+
+/// Values computed by a closure, cached by key.
+///
+/// A bounded memo clears itself when it is full, so its bound should leave room for the entries a
+/// recursion reuses most.
+pub struct Memo<K, V> {
+    cache: FastMap<K, V>,
+    capacity: Option<usize>,
+    stats: Stats,
+}
+
+impl<K: Eq + Hash + Debug, V: Clone> Memo<K, V> {
+    pub fn new() -> Self {
+        Self {
+            cache: FastMap::default(),
+            capacity: None,
+            stats: Stats::default(),
+        }
+    }
+
+    /// A memo holding at most `capacity` entries.
+    pub fn bounded(capacity: usize) -> Self {
+        Self {
+            capacity: Some(capacity),
+            ..Self::new()
+        }
+    }
+
+    /// Returns the cached value for `key`, or computes it with `f` and caches it. `f` gets the
+    /// memo for its own recursive lookups.
+    pub fn get(&mut self, key: K, f: impl FnOnce(&mut Self) -> V) -> V {
+        if let Some(value) = self.cache.get(&key) {
+            trace!("cache hit {key:?}");
+            self.stats.hits += 1;
+            return value.clone();
+        }
+        self.stats.misses += 1;
+        let value = f(self);
+        if self.capacity.is_some_and(|v| self.cache.len() >= v) {
+            self.stats.evictions += self.cache.len();
+            self.cache.clear();
+        }
+        if self.capacity != Some(0) {
+            self.cache.insert(key, value.clone());
+        }
+        value
+    }
+
+    pub fn stats(&self) -> Stats {
+        Stats {
+            entries: self.cache.len(),
+            ..self.stats
+        }
+    }
+}
+
+impl<K: Eq + Hash + Debug, V: Clone> Default for Memo<K, V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fib(memo: &mut Memo<u64, u64>, n: u64) -> u64 {
+        if n < 2 {
+            return n;
+        }
+        memo.get(n, |memo| fib(memo, n - 1) + fib(memo, n - 2))
+    }
+
+    #[test]
+    fn memo_fib() {
+        let mut memo = Memo::new();
+        assert_eq!(fib(&mut memo, 90), 2_880_067_194_370_816_120);
+        assert_eq!(
+            memo.stats(),
+            Stats {
+                hits: 87,
+                misses: 89,
+                evictions: 0,
+                entries: 89,
+            }
+        );
+        assert_eq!(fib(&mut memo, 90), 2_880_067_194_370_816_120);
+        assert_eq!(memo.stats().hits, 88);
+        assert_eq!(memo.stats().to_string(), "88 hits, 89 misses, 89 entries");
+    }
+
+    #[test]
+    fn memo_bounded() {
+        let mut memo = Memo::bounded(10);
+        assert_eq!(fib(&mut memo, 50), 12_586_269_025);
+        let stats = memo.stats();
+        assert!(stats.entries <= 10, "{stats:?}");
+        assert!(stats.evictions > 0, "{stats:?}");
+
+        let mut memo = Memo::bounded(0);
+        assert_eq!(fib(&mut memo, 20), 6765);
+        assert_eq!(memo.stats().entries, 0);
+    }
+}
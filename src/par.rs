@@ -0,0 +1,105 @@
+//! Spreading the lines of an input over worker threads, for days that handle every line on its
+//! own.
+//!
+//! The number of workers is process wide: every available core by default, or what the runner
+//! set with [`set_threads`].
+
+use std::{
+    iter::Sum,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Mutex, MutexGuard,
+    },
+};
+
+/// Zero for one worker per available core.
+static THREADS: AtomicUsize = AtomicUsize::new(0);
+
+/// Sets the number of workers, zero for one per available core.
+pub fn set_threads(threads: usize) {
+    THREADS.store(threads, Ordering::Relaxed);
+}
+
+/// Held by whoever changes the process wide settings, the worker count and
+/// [`crate::hash::set_map_seed`], for as long as it relies on them, so tests running at the same
+/// time don't change them underneath each other.
+pub fn lock_settings() -> MutexGuard<'static, ()> {
+    static LOCK: Mutex<()> = Mutex::new(());
+    LOCK.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+/// The number of workers [`map_reduce_lines`] uses.
+pub fn threads() -> usize {
+    match THREADS.load(Ordering::Relaxed) {
+        0 => std::thread::available_parallelism().map_or(1, usize::from),
+        v => v,
+    }
+}
+
+/// Maps every line of `data` and combines the results, or returns `None` if there are no lines.
+/// Every worker gets a contiguous block of lines and the blocks are combined in order, so
+/// `combine` has to be associative but not commutative.
+pub fn map_reduce_lines<T, M, C>(data: &str, map: M, combine: C) -> Option<T>
+where
+    T: Send,
+    M: Fn(&str) -> T + Sync,
+    C: Fn(T, T) -> T + Sync,
+{
+    let lines: Vec<&str> = data.trim().lines().collect();
+    let threads = threads().min(lines.len());
+    if threads <= 1 {
+        return lines.into_iter().map(map).reduce(combine);
+    }
+    let block = lines.len().div_ceil(threads);
+    std::thread::scope(|scope| {
+        let workers: Vec<_> = lines
+            .chunks(block)
+            .map(|block| scope.spawn(|| block.iter().map(|line| map(line)).reduce(&combine)))
+            .collect();
+        workers
+            .into_iter()
+            .filter_map(|worker| {
+                // Passed on so a panicking solver looks the same with and without workers
+                worker
+                    .join()
+                    .unwrap_or_else(|e| std::panic::resume_unwind(e))
+            })
+            .reduce(&combine)
+    })
+}
+
+/// Sums `map` over the lines of `data`.
+pub fn sum_lines<T, M>(data: &str, map: M) -> T
+where
+    T: Send + Sum,
+    M: Fn(&str) -> T + Sync,
+{
+    map_reduce_lines(data, map, |a, b| [a, b].into_iter().sum())
+        .unwrap_or_else(|| std::iter::empty().sum())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn par_map_reduce() {
+        let data: String = (1..=100).map(|v| format!("{v}\n")).collect();
+        let parse = |line: &str| line.parse::<u64>().unwrap();
+        // Not commutative, so this also checks the blocks are combined in order
+        let concat = |a: String, b: String| a + "," + &b;
+        let expected: Vec<String> = (1..=100).map(|v| v.to_string()).collect();
+        let _lock = lock_settings();
+        for threads in [1, 3, 8, 200] {
+            set_threads(threads);
+            assert_eq!(sum_lines(&data, parse), 5050);
+            assert_eq!(
+                map_reduce_lines(&data, str::to_owned, concat).unwrap(),
+                expected.join(",")
+            );
+            assert_eq!(sum_lines("\n", parse), 0);
+            assert_eq!(map_reduce_lines("", str::to_owned, concat), None);
+        }
+        set_threads(0);
+    }
+}
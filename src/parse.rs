@@ -0,0 +1,337 @@
+//! Parsing helpers for the input shapes that keep coming back. Everything borrows from the input,
+//! and errors point at the offending part of it so they can be reported as a line and column.
+
+use std::fmt;
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct ParseError<'a> {
+    /// The part of the input that could not be parsed. Empty when something was missing.
+    pub at: &'a str,
+    pub expected: String,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Position {
+    /// 1-based line number.
+    pub line: usize,
+    /// 1-based column, in characters.
+    pub column: usize,
+}
+
+impl<'a> ParseError<'a> {
+    fn new(at: &'a str, expected: impl Into<String>) -> Self {
+        Self {
+            at,
+            expected: expected.into(),
+        }
+    }
+
+    /// Where the error is in `input`, or `None` if the error did not come from parsing `input`.
+    pub fn position(&self, input: &str) -> Option<Position> {
+        let offset = (self.at.as_ptr() as usize).checked_sub(input.as_ptr() as usize)?;
+        let before = input.get(..offset)?;
+        let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+        Some(Position {
+            line: before.matches('\n').count() + 1,
+            column: before[line_start..].chars().count() + 1,
+        })
+    }
+}
+
+impl fmt::Display for ParseError<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let found = self.at.lines().next().unwrap_or("");
+        if found.is_empty() {
+            write!(f, "expected {}, found nothing", self.expected)
+        } else {
+            write!(f, "expected {}, found {found:?}", self.expected)
+        }
+    }
+}
+
+impl std::error::Error for ParseError<'_> {}
+
+impl fmt::Display for Position {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}, column {}", self.line, self.column)
+    }
+}
+
+/// Integer types the [`Scanner`] can read.
+pub trait Integer: Sized {
+    fn scan<'a>(scanner: &mut Scanner<'a>) -> Result<Self, ParseError<'a>>;
+}
+
+macro_rules! impl_integer {
+    ($via:ident, $($ty:ty),*) => {
+        $(
+            impl Integer for $ty {
+                fn scan<'a>(scanner: &mut Scanner<'a>) -> Result<Self, ParseError<'a>> {
+                    let start = scanner.pos;
+                    let value = scanner.$via()?;
+                    value.try_into().map_err(|_| {
+                        ParseError::new(
+                            &scanner.input[start..scanner.pos],
+                            concat!("integer that fits in ", stringify!($ty)),
+                        )
+                    })
+                }
+            }
+        )*
+    };
+}
+
+impl_integer!(unsigned_u64, u8, u16, u32, u64, usize);
+impl_integer!(signed_i64, i8, i16, i32, i64, isize);
+
+/// Reads values from the front of a string, keeping track of where it is.
+pub struct Scanner<'a> {
+    input: &'a str,
+    pos: usize,
+}
+
+impl<'a> Scanner<'a> {
+    pub fn new(input: &'a str) -> Self {
+        Self { input, pos: 0 }
+    }
+
+    /// The part of the input that has not been read yet.
+    pub fn rest(&self) -> &'a str {
+        &self.input[self.pos..]
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.pos == self.input.len()
+    }
+
+    pub fn skip_whitespace(&mut self) {
+        let rest = self.rest();
+        self.pos += rest.len() - rest.trim_start().len();
+    }
+
+    /// Consumes `literal`, which must come next.
+    pub fn literal(&mut self, literal: &str) -> Result<(), ParseError<'a>> {
+        if self.rest().starts_with(literal) {
+            self.pos += literal.len();
+            Ok(())
+        } else {
+            Err(self.error(format!("{literal:?}")))
+        }
+    }
+
+    pub fn integer<T: Integer>(&mut self) -> Result<T, ParseError<'a>> {
+        T::scan(self)
+    }
+
+    fn digits(&mut self) -> Result<&'a str, ParseError<'a>> {
+        let rest = self.rest();
+        let len = rest
+            .bytes()
+            .position(|b| !b.is_ascii_digit())
+            .unwrap_or(rest.len());
+        if len == 0 {
+            return Err(self.error("integer"));
+        }
+        self.pos += len;
+        Ok(&rest[..len])
+    }
+
+    fn unsigned_u64(&mut self) -> Result<u64, ParseError<'a>> {
+        let digits = self.digits()?;
+        digits
+            .bytes()
+            .try_fold(0u64, |acc, b| {
+                acc.checked_mul(10)?.checked_add(u64::from(b - b'0'))
+            })
+            .ok_or_else(|| ParseError::new(digits, "integer that fits in u64"))
+    }
+
+    fn signed_i64(&mut self) -> Result<i64, ParseError<'a>> {
+        let start = self.pos;
+        let negative = match self.rest().as_bytes().first() {
+            Some(b'-') => true,
+            Some(b'+') => false,
+            _ => return self.signed_magnitude(start, false),
+        };
+        self.pos += 1;
+        self.signed_magnitude(start, negative)
+    }
+
+    fn signed_magnitude(&mut self, start: usize, negative: bool) -> Result<i64, ParseError<'a>> {
+        let magnitude = self.unsigned_u64()?;
+        let value = if negative {
+            0i64.checked_sub_unsigned(magnitude)
+        } else {
+            i64::try_from(magnitude).ok()
+        };
+        value.ok_or_else(|| {
+            ParseError::new(&self.input[start..self.pos], "integer that fits in i64")
+        })
+    }
+
+    /// An error pointing at the next whitespace separated token.
+    fn error(&self, expected: impl Into<String>) -> ParseError<'a> {
+        let rest = self.rest();
+        let len = rest
+            .find(|c: char| c.is_ascii_whitespace())
+            .unwrap_or(rest.len());
+        ParseError::new(&rest[..len], expected)
+    }
+}
+
+/// Whitespace separated integers, like `"79 14  55 13"`.
+pub fn integers<T: Integer>(input: &str) -> Result<Vec<T>, ParseError<'_>> {
+    let mut scanner = Scanner::new(input);
+    let mut ret = Vec::new();
+    loop {
+        scanner.skip_whitespace();
+        if scanner.is_empty() {
+            return Ok(ret);
+        }
+        ret.push(scanner.integer()?);
+        if !scanner.is_empty()
+            && !scanner
+                .rest()
+                .starts_with(|c: char| c.is_ascii_whitespace())
+        {
+            return Err(scanner.error("whitespace"));
+        }
+    }
+}
+
+/// A single integer, optionally surrounded by whitespace.
+pub fn integer<T: Integer>(input: &str) -> Result<T, ParseError<'_>> {
+    let [ret] = integer_array(input)?;
+    Ok(ret)
+}
+
+/// Exactly `N` whitespace separated integers.
+pub fn integer_array<T: Integer, const N: usize>(input: &str) -> Result<[T; N], ParseError<'_>> {
+    let values = integers(input)?;
+    let len = values.len();
+    values
+        .try_into()
+        .map_err(|_| ParseError::new(input, format!("{N} integers instead of {len}")))
+}
+
+/// The part of `line` after `prefix`.
+pub fn prefixed<'a>(line: &'a str, prefix: &str) -> Result<&'a str, ParseError<'a>> {
+    line.strip_prefix(prefix)
+        .ok_or_else(|| ParseError::new(line, format!("{prefix:?}")))
+}
+
+/// A header followed by whitespace separated integers, like `"Time:      7  15   30"`.
+pub fn prefixed_integers<'a, T: Integer>(
+    line: &'a str,
+    prefix: &str,
+) -> Result<Vec<T>, ParseError<'a>> {
+    integers(prefixed(line, prefix)?)
+}
+
+/// Splits a `key: value` line.
+pub fn key_value(line: &str) -> Result<(&str, &str), ParseError<'_>> {
+    line.split_once(": ")
+        .ok_or_else(|| ParseError::new(line, "`key: value`"))
+}
+
+/// The blocks of lines separated by blank lines. Leading and trailing blank lines are ignored.
+pub fn sections(input: &str) -> impl Iterator<Item = &str> {
+    input
+        .trim_matches('\n')
+        .split("\n\n")
+        .map(|v| v.trim_matches('\n'))
+        .filter(|v| !v.is_empty())
+}
+
+/// Splits `line` into columns of `width` bytes. The last column may be shorter.
+pub fn columns(line: &str, width: usize) -> Result<Vec<&str>, ParseError<'_>> {
+    assert!(width > 0);
+    (0..line.len())
+        .step_by(width)
+        .map(|i| {
+            line.get(i..(i + width).min(line.len()))
+                .ok_or_else(|| ParseError::new(line.get(i..).unwrap_or(line), "ASCII column"))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_integers() {
+        assert_eq!(integers::<u32>("  79 14  55 13 "), Ok(vec![79, 14, 55, 13]));
+        assert_eq!(integers::<i64>("-3 +4 5"), Ok(vec![-3, 4, 5]));
+        assert_eq!(integers::<u8>(""), Ok(vec![]));
+        assert_eq!(integer_array::<usize, 3>("50 98 2"), Ok([50, 98, 2]));
+        assert_eq!(integer::<usize>(" 6 "), Ok(6));
+
+        let err = integers::<u8>("1 2x 3").unwrap_err();
+        assert_eq!((err.at, err.expected.as_str()), ("x", "whitespace"));
+        let err = integers::<u8>("1 256").unwrap_err();
+        assert_eq!(
+            (err.at, err.expected.as_str()),
+            ("256", "integer that fits in u8")
+        );
+        let err = integers::<u32>("-1").unwrap_err();
+        assert_eq!((err.at, err.expected.as_str()), ("-1", "integer"));
+        let err = integer_array::<u32, 3>("1 2").unwrap_err();
+        assert_eq!(err.expected, "3 integers instead of 2");
+    }
+
+    #[test]
+    fn parse_scanner_limits() {
+        let mut s = Scanner::new("18446744073709551615 18446744073709551616");
+        assert_eq!(s.integer::<u64>(), Ok(u64::MAX));
+        s.skip_whitespace();
+        assert!(s.integer::<u64>().is_err());
+
+        let mut s = Scanner::new("-9223372036854775808,-9223372036854775809");
+        assert_eq!(s.integer::<i64>(), Ok(i64::MIN));
+        s.literal(",").unwrap();
+        assert_eq!(s.integer::<i64>().unwrap_err().at, "-9223372036854775809");
+    }
+
+    #[test]
+    fn parse_position() {
+        let input = "Time:      7  15   30\nDistance:  9  4O  200";
+        let line = input.lines().nth(1).unwrap();
+        let err = prefixed_integers::<u32>(line, "Distance:").unwrap_err();
+        assert_eq!(err.at, "O");
+        assert_eq!(
+            err.position(input),
+            Some(Position {
+                line: 2,
+                column: 16
+            })
+        );
+        assert_eq!(err.position("unrelated"), None);
+        assert_eq!(err.to_string(), "expected whitespace, found \"O\"");
+    }
+
+    #[test]
+    fn parse_prefixed() {
+        assert_eq!(
+            prefixed_integers::<u64>("seeds: 79 14", "seeds: "),
+            Ok(vec![79, 14])
+        );
+        let err = prefixed_integers::<u64>("sneeds: 79", "seeds: ").unwrap_err();
+        assert_eq!(err.at, "sneeds: 79");
+        assert_eq!(key_value("Card 1: 41 48"), Ok(("Card 1", "41 48")));
+        assert!(key_value("Card 1 41").is_err());
+    }
+
+    #[test]
+    fn parse_sections() {
+        let res: Vec<_> = sections("\na\nb\n\nc\n\n\nd\n").collect();
+        assert_eq!(res, ["a\nb", "c", "d"]);
+    }
+
+    #[test]
+    fn parse_columns() {
+        assert_eq!(columns("41 48  6", 3), Ok(vec!["41 ", "48 ", " 6"]));
+        assert_eq!(columns("", 3), Ok(vec![]));
+        assert!(columns("ab\u{e9}", 3).is_err());
+    }
+}
@@ -0,0 +1,294 @@
+//! Sets of integers stored as sorted, disjoint half-open ranges.
+
+use std::ops::Range;
+
+use crate::arith::{self, Integer};
+
+/// A set of integers as a list of ranges. The ranges are kept sorted, non-empty and separated
+/// by at least one value, so equal sets always have equal representations.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct RangeSet<T> {
+    ranges: Vec<Range<T>>,
+}
+
+impl<T> Default for RangeSet<T> {
+    fn default() -> Self {
+        Self { ranges: Vec::new() }
+    }
+}
+
+impl<T: Integer + Ord> RangeSet<T> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ranges.is_empty()
+    }
+
+    /// Number of values in the set.
+    pub fn len(&self) -> T {
+        arith::sum(self.ranges.iter().map(|v| arith::sub(v.end, v.start)))
+    }
+
+    pub fn contains(&self, value: T) -> bool {
+        let i = self.ranges.partition_point(|v| v.end <= value);
+        self.ranges.get(i).is_some_and(|v| v.start <= value)
+    }
+
+    /// The disjoint ranges in increasing order.
+    pub fn iter(&self) -> impl Iterator<Item = Range<T>> + '_ {
+        self.ranges.iter().cloned()
+    }
+
+    pub fn insert(&mut self, range: Range<T>) {
+        if range.is_empty() {
+            return;
+        }
+        // Everything from `i` up to `j` overlaps or touches the new range
+        let i = self.ranges.partition_point(|v| v.end < range.start);
+        let j = self.ranges.partition_point(|v| v.start <= range.end);
+        let merged = if i < j {
+            range.start.min(self.ranges[i].start)..range.end.max(self.ranges[j - 1].end)
+        } else {
+            range
+        };
+        self.ranges.splice(i..j, [merged]);
+    }
+
+    pub fn remove(&mut self, range: Range<T>) {
+        if range.is_empty() {
+            return;
+        }
+        // Everything from `i` up to `j` overlaps the removed range
+        let i = self.ranges.partition_point(|v| v.end <= range.start);
+        let j = self.ranges.partition_point(|v| v.start < range.end);
+        if i == j {
+            return;
+        }
+        let left = self.ranges[i].start..range.start;
+        let right = range.end..self.ranges[j - 1].end;
+        let kept = [left, right].into_iter().filter(|v| !v.is_empty());
+        self.ranges.splice(i..j, kept);
+    }
+
+    pub fn union(&self, other: &Self) -> Self {
+        let mut ret = self.clone();
+        ret.extend(other.iter());
+        ret
+    }
+
+    pub fn intersection(&self, other: &Self) -> Self {
+        let mut ranges = Vec::new();
+        let (mut a, mut b) = (
+            self.ranges.iter().peekable(),
+            other.ranges.iter().peekable(),
+        );
+        while let (Some(x), Some(y)) = (a.peek(), b.peek()) {
+            let overlap = x.start.max(y.start)..x.end.min(y.end);
+            if !overlap.is_empty() {
+                ranges.push(overlap);
+            }
+            if x.end < y.end {
+                a.next();
+            } else {
+                b.next();
+            }
+        }
+        Self { ranges }
+    }
+
+    pub fn difference(&self, other: &Self) -> Self {
+        let mut ret = self.clone();
+        for range in other.iter() {
+            ret.remove(range);
+        }
+        ret
+    }
+
+    /// The ranges of the set cut at every breakpoint, so that no piece contains a breakpoint
+    /// other than as its start.
+    pub fn split(&self, breakpoints: impl IntoIterator<Item = T>) -> Vec<Range<T>> {
+        let mut breakpoints: Vec<T> = breakpoints.into_iter().collect();
+        breakpoints.sort_unstable();
+        breakpoints.dedup();
+        let mut ret = Vec::new();
+        for range in self.iter() {
+            let first = breakpoints.partition_point(|&v| v <= range.start);
+            let mut start = range.start;
+            for &cut in breakpoints[first..].iter().take_while(|&&v| v < range.end) {
+                ret.push(start..cut);
+                start = cut;
+            }
+            ret.push(start..range.end);
+        }
+        ret
+    }
+
+    /// Moves every value by the distance from `from` to `to`.
+    pub fn shift(&self, from: T, to: T) -> Self {
+        let shift = |v: T| {
+            if to >= from {
+                arith::add(v, arith::sub(to, from))
+            } else {
+                arith::sub(v, arith::sub(from, to))
+            }
+        };
+        Self {
+            ranges: self.iter().map(|v| shift(v.start)..shift(v.end)).collect(),
+        }
+    }
+}
+
+impl<T: Integer + Ord> Extend<Range<T>> for RangeSet<T> {
+    fn extend<I: IntoIterator<Item = Range<T>>>(&mut self, iter: I) {
+        for range in iter {
+            self.insert(range);
+        }
+    }
+}
+
+impl<T: Integer + Ord> FromIterator<Range<T>> for RangeSet<T> {
+    fn from_iter<I: IntoIterator<Item = Range<T>>>(iter: I) -> Self {
+        let mut ret = Self::new();
+        ret.extend(iter);
+        ret
+    }
+}
+
+impl<T: Integer + Ord> From<Range<T>> for RangeSet<T> {
+    fn from(range: Range<T>) -> Self {
+        Self::from_iter([range])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Every set is checked against a bitmask of the same values, over all subsets of `0..BITS`.
+    const BITS: u32 = 7;
+
+    fn all_ranges() -> impl Iterator<Item = Range<u32>> {
+        (0..=BITS).flat_map(|start| (start..=BITS).map(move |end| start..end))
+    }
+
+    fn mask(range: Range<u32>) -> u32 {
+        range.fold(0, |acc, v| acc | 1 << v)
+    }
+
+    /// Builds the set from single values in a scrambled order, to exercise merging.
+    fn from_mask(mask: u32) -> RangeSet<u32> {
+        let mut ret = RangeSet::new();
+        for v in (0..BITS).map(|v| (v * 3) % BITS) {
+            if mask & 1 << v != 0 {
+                ret.insert(v..v + 1);
+            }
+        }
+        check(&ret, mask);
+        ret
+    }
+
+    fn check(set: &RangeSet<u32>, expected: u32) {
+        for pair in set.ranges.windows(2) {
+            assert!(pair[0].end < pair[1].start, "{set:?}");
+        }
+        assert!(set.ranges.iter().all(|v| !v.is_empty()), "{set:?}");
+        let actual = set.iter().fold(0, |acc, v| acc | mask(v));
+        assert_eq!(actual, expected, "{set:?}");
+        assert_eq!(set.len(), expected.count_ones());
+        assert_eq!(set.is_empty(), expected == 0);
+        for v in 0..32 {
+            assert_eq!(
+                set.contains(v),
+                expected & 1 << v != 0,
+                "{set:?} contains {v}"
+            );
+        }
+    }
+
+    #[test]
+    fn range_insert_remove() {
+        for m in 0..1 << BITS {
+            let set = from_mask(m);
+            for range in all_ranges() {
+                let mut inserted = set.clone();
+                inserted.insert(range.clone());
+                check(&inserted, m | mask(range.clone()));
+                let mut removed = set.clone();
+                removed.remove(range.clone());
+                check(&removed, m & !mask(range));
+            }
+        }
+    }
+
+    #[test]
+    fn range_set_operations() {
+        for a in 0..1 << BITS {
+            let set_a = from_mask(a);
+            for b in 0..1 << BITS {
+                let set_b = from_mask(b);
+                check(&set_a.union(&set_b), a | b);
+                check(&set_a.intersection(&set_b), a & b);
+                check(&set_a.difference(&set_b), a & !b);
+            }
+        }
+    }
+
+    #[test]
+    fn range_split() {
+        for m in 0..1 << BITS {
+            let set = from_mask(m);
+            for cuts in 0..1u32 << (BITS + 1) {
+                let breakpoints: Vec<u32> =
+                    (0..=BITS).rev().filter(|v| cuts & 1 << v != 0).collect();
+                let pieces = set.split(breakpoints.iter().copied());
+                let mut covered = 0;
+                for (i, piece) in pieces.iter().enumerate() {
+                    assert!(!piece.is_empty());
+                    assert_eq!(covered & mask(piece.clone()), 0, "overlap in {pieces:?}");
+                    covered |= mask(piece.clone());
+                    assert!(
+                        !breakpoints
+                            .iter()
+                            .any(|&v| piece.start < v && v < piece.end),
+                        "{piece:?} crosses a breakpoint of {breakpoints:?}"
+                    );
+                    // Adjacent pieces only where a breakpoint separates them
+                    if let Some(next) = pieces.get(i + 1) {
+                        assert!(piece.end <= next.start);
+                        assert!(piece.end < next.start || breakpoints.contains(&next.start));
+                    }
+                }
+                assert_eq!(covered, m);
+            }
+        }
+    }
+
+    #[test]
+    fn range_shift() {
+        for m in 0..1 << BITS {
+            let set = from_mask(m);
+            for from in 0..BITS {
+                for to in 0..BITS {
+                    let expected = if to >= from {
+                        m << (to - from)
+                    } else {
+                        // Values below zero can't be represented, so only shift sets that stay
+                        if m.trailing_zeros() < from - to {
+                            continue;
+                        }
+                        m >> (from - to)
+                    };
+                    check(&set.shift(from, to), expected);
+                }
+            }
+        }
+        let set: RangeSet<usize> = [5..10, 0..2, 10..12].into_iter().collect();
+        assert_eq!(set.iter().collect::<Vec<_>>(), [0..2, 5..12]);
+        assert_eq!(
+            set.shift(0, 100).iter().collect::<Vec<_>>(),
+            [100..102, 105..112]
+        );
+    }
+}
@@ -0,0 +1,195 @@
+//! Interactive sessions for poking at a day's parsed input.
+//!
+//! A day opts in by building a [`Commands`] table around its parsed data. The generic loop in
+//! [`run`] adds `help`, `history`, `!N` to repeat an earlier command, and `quit`.
+
+use std::io::{BufRead, Write};
+
+pub type CommandFn<T> = fn(&T, &[&str]) -> Result<String, String>;
+
+/// A day's commands, as seen by the loop.
+pub trait Session {
+    /// `(usage, description)` for every command.
+    fn help(&self) -> Vec<(&'static str, &'static str)>;
+
+    /// Runs a command, or returns `None` if there is no command called `name`.
+    fn execute(&self, name: &str, args: &[&str]) -> Option<Result<String, String>>;
+}
+
+struct Command<T> {
+    usage: &'static str,
+    description: &'static str,
+    run: CommandFn<T>,
+}
+
+/// Commands operating on some parsed `T`.
+pub struct Commands<T> {
+    state: T,
+    commands: Vec<Command<T>>,
+}
+
+impl<T> Commands<T> {
+    pub fn new(state: T) -> Self {
+        Self {
+            state,
+            commands: Vec::new(),
+        }
+    }
+
+    /// Registers a command. Its name is the first word of `usage`.
+    pub fn command(
+        mut self,
+        usage: &'static str,
+        description: &'static str,
+        run: CommandFn<T>,
+    ) -> Self {
+        self.commands.push(Command {
+            usage,
+            description,
+            run,
+        });
+        self
+    }
+}
+
+impl<T> Session for Commands<T> {
+    fn help(&self) -> Vec<(&'static str, &'static str)> {
+        self.commands
+            .iter()
+            .map(|c| (c.usage, c.description))
+            .collect()
+    }
+
+    fn execute(&self, name: &str, args: &[&str]) -> Option<Result<String, String>> {
+        let cmd = self
+            .commands
+            .iter()
+            .find(|c| c.usage.split(' ').next() == Some(name))?;
+        Some((cmd.run)(&self.state, args))
+    }
+}
+
+const BUILTINS: &[(&str, &str)] = &[
+    ("help", "Show this help"),
+    ("history", "List previous commands"),
+    ("!N", "Repeat command N from the history"),
+    ("quit", "Leave"),
+];
+
+/// Reads commands from `input` until it ends or `quit` is entered.
+pub fn run(
+    session: &dyn Session,
+    input: impl BufRead,
+    mut output: impl Write,
+    prompt: &str,
+) -> std::io::Result<()> {
+    let mut history: Vec<String> = Vec::new();
+    let mut lines = input.lines();
+    loop {
+        write!(output, "{prompt}")?;
+        output.flush()?;
+        let Some(line) = lines.next().transpose()? else {
+            writeln!(output)?;
+            return Ok(());
+        };
+        let mut line = line.trim().to_owned();
+
+        if let Some(n) = line.strip_prefix('!') {
+            match n
+                .parse::<usize>()
+                .ok()
+                .and_then(|n| history.get(n.wrapping_sub(1)))
+            {
+                Some(prev) => {
+                    line = prev.clone();
+                    writeln!(output, "{line}")?;
+                }
+                None => {
+                    writeln!(output, "error: no command {n:?} in the history")?;
+                    continue;
+                }
+            }
+        }
+
+        let words: Vec<_> = line.split_whitespace().collect();
+        let Some((&name, args)) = words.split_first() else {
+            continue;
+        };
+        match name {
+            "quit" | "exit" => return Ok(()),
+            "help" => {
+                let commands = session.help();
+                let width = commands
+                    .iter()
+                    .chain(BUILTINS)
+                    .map(|(usage, _)| usage.len())
+                    .max()
+                    .unwrap_or(0);
+                for (usage, description) in commands.iter().chain(BUILTINS) {
+                    writeln!(output, "  {usage:width$}  {description}")?;
+                }
+            }
+            "history" => {
+                for (i, prev) in history.iter().enumerate() {
+                    writeln!(output, "{:>4}  {prev}", i + 1)?;
+                }
+            }
+            _ => match session.execute(name, args) {
+                Some(Ok(res)) => writeln!(output, "{}", res.trim_end())?,
+                Some(Err(e)) => writeln!(output, "error: {e}")?,
+                None => writeln!(output, "error: unknown command {name:?}, try `help`")?,
+            },
+        }
+        if name != "history" {
+            history.push(line);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn session() -> Commands<Vec<i32>> {
+        Commands::new(vec![3, 1, 4]).command("get INDEX", "Value at INDEX", |v, args| {
+            let i: usize = args
+                .first()
+                .and_then(|v| v.parse().ok())
+                .ok_or("expected an index")?;
+            v.get(i)
+                .map(|v| v.to_string())
+                .ok_or_else(|| format!("index {i} out of range"))
+        })
+    }
+
+    fn run_script(script: &str) -> String {
+        let mut out = Vec::new();
+        run(&session(), script.as_bytes(), &mut out, "> ").unwrap();
+        String::from_utf8(out).unwrap()
+    }
+
+    #[test]
+    fn repl_commands() {
+        assert_eq!(
+            run_script("get 2\nget 5\nget\nfoo\n\nquit\nget 0\n"),
+            "> 4\n> error: index 5 out of range\n> error: expected an index\n\
+             > error: unknown command \"foo\", try `help`\n> > "
+        );
+    }
+
+    #[test]
+    fn repl_history() {
+        assert_eq!(
+            run_script("get 0\nget 1\nhistory\n!1\n!9\n"),
+            "> 3\n> 1\n>    1  get 0\n   2  get 1\n> get 0\n3\n\
+             > error: no command \"9\" in the history\n> \n"
+        );
+    }
+
+    #[test]
+    fn repl_help() {
+        let res = run_script("help");
+        assert!(res.contains("  get INDEX  Value at INDEX\n"), "{res}");
+        assert!(res.contains("  quit       Leave\n"), "{res}");
+    }
+}
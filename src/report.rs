@@ -0,0 +1,421 @@
+//! The results summary: which days are solved, hashes of their answers, timings, allocations and
+//! whether the answers match the known ones. Rendered as Markdown and as a standalone HTML page,
+//! both with an inline SVG chart of the runtimes.
+
+use std::{fmt, fmt::Write as _, time::Duration};
+
+use crate::{
+    days::{Day, Event, Step},
+    limits::Failure,
+};
+
+const TITLE: &str = "Advent of Code results";
+
+#[derive(Clone, Copy, PartialEq, Eq, Default, Debug)]
+pub struct Cost {
+    pub elapsed: Duration,
+    pub allocations: usize,
+}
+
+/// The results of running one day.
+#[derive(Clone)]
+pub struct Row {
+    pub day: &'static Day,
+    /// Whether there was an input to run the day on.
+    pub has_input: bool,
+    pub parse: Option<Cost>,
+    pub parts: [Option<(String, Cost)>; 2],
+    pub failure: Option<(Step, Failure)>,
+    /// The known answers, see [`Day::answers_path`].
+    pub expected: Option<Vec<String>>,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Status {
+    Verified,
+    Wrong,
+    Unverified,
+    Failed,
+    NoInput,
+}
+
+impl fmt::Display for Status {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Verified => "verified",
+            Self::Wrong => "WRONG",
+            Self::Unverified => "unverified",
+            Self::Failed => "failed",
+            Self::NoInput => "no input",
+        })
+    }
+}
+
+impl Row {
+    pub fn new(day: &'static Day, has_input: bool) -> Self {
+        Self {
+            day,
+            has_input,
+            parse: None,
+            parts: [None, None],
+            failure: None,
+            expected: None,
+        }
+    }
+
+    /// Takes the results from an event of running the day.
+    pub fn record(&mut self, event: Event) {
+        let Event::Finished {
+            step,
+            answer,
+            elapsed,
+            allocations,
+        } = event
+        else {
+            return;
+        };
+        let cost = Cost {
+            elapsed,
+            allocations,
+        };
+        match step {
+            Step::Parse => self.parse = Some(cost),
+            Step::Part(part) => {
+                self.parts[usize::from(part - 1)] = Some((answer.unwrap_or_default(), cost));
+            }
+        }
+    }
+
+    pub fn status(&self) -> Status {
+        if !self.has_input {
+            return Status::NoInput;
+        }
+        if self.failure.is_some() {
+            return Status::Failed;
+        }
+        let Some(expected) = &self.expected else {
+            return Status::Unverified;
+        };
+        let matches = expected
+            .iter()
+            .zip(&self.parts)
+            .all(|(expected, part)| part.as_ref().is_some_and(|(answer, _)| answer == expected));
+        // An answer for every part, no more and no less
+        if !matches || expected.len() > self.parts.len() {
+            Status::Wrong
+        } else if expected.len() < self.parts.len() {
+            Status::Unverified
+        } else {
+            Status::Verified
+        }
+    }
+
+    /// Time spent on all steps that finished.
+    pub fn total(&self) -> Duration {
+        let parts = self.parts.iter().flatten().map(|(_, cost)| cost.elapsed);
+        self.parse.map(|v| v.elapsed).into_iter().chain(parts).sum()
+    }
+
+    /// The table cells, without any escaping.
+    fn cells(&self) -> [String; 8] {
+        let cost = |step: Step, cost: Option<Cost>| match (cost, self.failure) {
+            (Some(cost), _) => format!("{:?}", cost.elapsed),
+            (None, Some((failed, failure))) if failed == step => failure.to_string(),
+            (None, _) => "-".to_owned(),
+        };
+        let allocations = [self.parse]
+            .into_iter()
+            .chain(self.parts.iter().map(|v| v.as_ref().map(|(_, cost)| *cost)))
+            .map(|cost| cost.map_or("-".to_owned(), |v| v.allocations.to_string()))
+            .collect::<Vec<_>>()
+            .join(" / ");
+        let answers = self
+            .parts
+            .iter()
+            .flatten()
+            .map(|(answer, _)| answer_hash(answer))
+            .collect::<Vec<_>>()
+            .join(" ");
+        [
+            self.day.to_string(),
+            self.day.title.to_owned(),
+            cost(Step::Parse, self.parse),
+            cost(Step::Part(1), self.parts[0].as_ref().map(|v| v.1)),
+            cost(Step::Part(2), self.parts[1].as_ref().map(|v| v.1)),
+            allocations,
+            answers,
+            self.status().to_string(),
+        ]
+    }
+}
+
+const HEADER: [&str; 8] = [
+    "Day",
+    "Title",
+    "Parse",
+    "Part 1",
+    "Part 2",
+    "Allocations",
+    "Answer hashes",
+    "Status",
+];
+
+/// A short hash of an answer, to compare answers without publishing them. FNV-1a, so it stays
+/// the same across Rust versions.
+pub fn answer_hash(answer: &str) -> String {
+    let hash = answer.bytes().fold(0xcbf2_9ce4_8422_2325u64, |hash, b| {
+        (hash ^ u64::from(b)).wrapping_mul(0x0100_0000_01b3)
+    });
+    format!("{:08x}", hash >> 32)
+}
+
+fn summary(rows: &[Row]) -> String {
+    let verified = rows
+        .iter()
+        .filter(|v| v.status() == Status::Verified)
+        .count();
+    format!("{verified} of {} days verified", rows.len())
+}
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// A horizontal bar per day, colored by status.
+fn chart(rows: &[Row]) -> String {
+    const ROW_HEIGHT: usize = 22;
+    const LABEL_WIDTH: usize = 110;
+    const BAR_WIDTH: f64 = 400.0;
+
+    let max = rows.iter().map(Row::total).max().unwrap_or_default();
+    let mut ret = String::new();
+    let _ = writeln!(
+        ret,
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{}" height="{}" font-family="sans-serif" font-size="12">"#,
+        LABEL_WIDTH + BAR_WIDTH as usize + 100,
+        rows.len() * ROW_HEIGHT
+    );
+    for (i, row) in rows.iter().enumerate() {
+        let y = i * ROW_HEIGHT;
+        let total = row.total();
+        let width = if total.is_zero() {
+            0.0
+        } else {
+            (total.as_secs_f64() / max.as_secs_f64() * BAR_WIDTH)
+                .round()
+                .max(1.0)
+        };
+        let color = match row.status() {
+            Status::Verified => "#4c9a2a",
+            Status::Wrong | Status::Failed => "#c62828",
+            Status::Unverified | Status::NoInput => "#888888",
+        };
+        let _ = writeln!(
+            ret,
+            r#"  <text x="{}" y="{}" text-anchor="end">{}</text>"#,
+            LABEL_WIDTH - 6,
+            y + 15,
+            row.day
+        );
+        let _ = writeln!(
+            ret,
+            r#"  <rect x="{LABEL_WIDTH}" y="{}" width="{width}" height="18" fill="{color}"/>"#,
+            y + 2
+        );
+        let label = if row.has_input {
+            format!("{total:?}")
+        } else {
+            "-".to_owned()
+        };
+        let _ = writeln!(
+            ret,
+            r#"  <text x="{}" y="{}">{label}</text>"#,
+            LABEL_WIDTH + width as usize + 6,
+            y + 15
+        );
+    }
+    ret.push_str("</svg>\n");
+    ret
+}
+
+pub fn markdown(rows: &[Row], commit: &str) -> String {
+    let mut ret = format!("# {TITLE}\n\nCommit `{commit}`, {}.\n\n", summary(rows));
+    let _ = writeln!(ret, "| {} |", HEADER.join(" | "));
+    let _ = writeln!(ret, "|---|---|---:|---:|---:|---:|---|---|");
+    for row in rows {
+        let cells = row.cells().map(|v| v.replace('|', "\\|"));
+        let _ = writeln!(ret, "| {} |", cells.join(" | "));
+    }
+    ret.push('\n');
+    ret.push_str(&chart(rows));
+    ret
+}
+
+pub fn html(rows: &[Row], commit: &str) -> String {
+    let mut ret = format!(
+        r#"<!DOCTYPE html>
+<html lang="en">
+<head>
+<meta charset="utf-8">
+<title>{TITLE}</title>
+<style>
+body {{ font-family: sans-serif; margin: 2em; }}
+table {{ border-collapse: collapse; }}
+th, td {{ padding: 0.2em 0.6em; border-bottom: 1px solid #ddd; text-align: left; }}
+td.num {{ text-align: right; font-variant-numeric: tabular-nums; }}
+.verified {{ color: #2e7d32; }}
+.wrong, .failed {{ color: #c62828; }}
+.unverified, .no-input {{ color: #777; }}
+</style>
+</head>
+<body>
+<h1>{TITLE}</h1>
+<p>Commit <code>{}</code>, {}.</p>
+<table>
+"#,
+        escape_html(commit),
+        summary(rows)
+    );
+    let header: String = HEADER.iter().map(|v| format!("<th>{v}</th>")).collect();
+    let _ = writeln!(ret, "<tr>{header}</tr>");
+    for row in rows {
+        let class = row.status().to_string().to_lowercase().replace(' ', "-");
+        let cells: String = row
+            .cells()
+            .iter()
+            .enumerate()
+            .map(|(i, cell)| {
+                let cell = escape_html(cell);
+                match i {
+                    2..=5 => format!(r#"<td class="num">{cell}</td>"#),
+                    6 => format!("<td><code>{cell}</code></td>"),
+                    7 => format!(r#"<td class="{class}">{cell}</td>"#),
+                    _ => format!("<td>{cell}</td>"),
+                }
+            })
+            .collect();
+        let _ = writeln!(ret, "<tr>{cells}</tr>");
+    }
+    ret.push_str("</table>\n<h2>Runtime</h2>\n");
+    ret.push_str(&chart(rows));
+    ret.push_str("</body>\n</html>\n");
+    ret
+}
+
+/// Reads the known answers of `day`, if there are any, and rejects files with more answers than
+/// a day has parts.
+pub fn expected_answers(day: &Day) -> Result<Option<Vec<String>>, String> {
+    let path = day.answers_path();
+    let Ok(data) = std::fs::read_to_string(&path) else {
+        return Ok(None);
+    };
+    parse_answers(&data)
+        .map(Some)
+        .map_err(|e| format!("{path}: {e}"))
+}
+
+/// One answer per line, skipping blank lines.
+fn parse_answers(data: &str) -> Result<Vec<String>, String> {
+    let answers: Vec<String> = data
+        .lines()
+        .map(str::trim)
+        .filter(|v| !v.is_empty())
+        .map(str::to_owned)
+        .collect();
+    if answers.len() > 2 {
+        return Err(format!(
+            "{} answers, but a day only has 2 parts",
+            answers.len()
+        ));
+    }
+    Ok(answers)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{days, snapshot::assert_snapshot};
+
+    fn finished(step: Step, answer: Option<&str>, micros: u64, allocations: usize) -> Event {
+        Event::Finished {
+            step,
+            answer: answer.map(str::to_owned),
+            elapsed: Duration::from_micros(micros),
+            allocations,
+        }
+    }
+
+    fn rows() -> Vec<Row> {
+        let day = |day| days::find(2023, day).unwrap();
+
+        let mut verified = Row::new(day(5), true);
+        verified.record(finished(Step::Parse, None, 50, 30));
+        verified.record(finished(Step::Part(1), Some("35"), 10, 2));
+        verified.record(finished(Step::Part(2), Some("46"), 20, 3));
+        verified.expected = Some(vec!["35".into(), "46".into()]);
+
+        let mut wrong = Row::new(day(6), true);
+        wrong.record(finished(Step::Part(1), Some("288"), 5, 1));
+        wrong.record(finished(Step::Part(2), Some("71503"), 7, 1));
+        wrong.expected = Some(vec!["288".into(), "1".into()]);
+
+        let mut timeout = Row::new(day(8), true);
+        timeout.record(finished(Step::Parse, None, 40, 12));
+        timeout.record(finished(Step::Part(1), Some("6"), 1000, 0));
+        timeout.failure = Some((Step::Part(2), Failure::Timeout(Duration::from_secs(60))));
+
+        vec![verified, wrong, timeout, Row::new(day(9), false)]
+    }
+
+    #[test]
+    fn report_status() {
+        let status: Vec<_> = rows().iter().map(Row::status).collect();
+        assert_eq!(
+            status,
+            [
+                Status::Verified,
+                Status::Wrong,
+                Status::Failed,
+                Status::NoInput
+            ]
+        );
+
+        // Part 2 is never checked with only one answer, and extra answers are a mistake
+        let mut row = rows().swap_remove(0);
+        row.expected = Some(vec!["35".into()]);
+        assert_eq!(row.status(), Status::Unverified);
+        row.expected = Some(vec!["35".into(), "46".into(), "1".into()]);
+        assert_eq!(row.status(), Status::Wrong);
+        row.expected = Some(Vec::new());
+        assert_eq!(row.status(), Status::Unverified);
+
+        assert_eq!(answer_hash("35"), answer_hash("35"));
+        assert_ne!(answer_hash("35"), answer_hash("46"));
+    }
+
+    #[test]
+    fn report_parse_answers() {
+        assert_eq!(
+            parse_answers("12\n\n 34 \n\n"),
+            Ok(vec!["12".into(), "34".into()])
+        );
+        assert_eq!(parse_answers("12\n"), Ok(vec!["12".into()]));
+        assert_eq!(
+            parse_answers("1\n2\n3\n"),
+            Err("3 answers, but a day only has 2 parts".into())
+        );
+    }
+
+    #[test]
+    fn report_markdown() {
+        assert_snapshot("report_markdown", &markdown(&rows(), "abc1234"));
+    }
+
+    #[test]
+    fn report_html() {
+        assert_snapshot("report_html", &html(&rows(), "abc1234"));
+    }
+}
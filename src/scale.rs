@@ -0,0 +1,166 @@
+//! How solutions scale: timing every step on generated inputs of growing size, and fitting an
+//! empirical growth exponent to the timings.
+
+use std::time::Duration;
+
+use crate::{
+    days::{Event, Solve, Step},
+    limits::{self, Failure, Limits},
+};
+
+/// A small deterministic random number generator (SplitMix64) for input generators, so the same
+/// seed always gives the same input.
+pub struct Rng(u64);
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        Self(seed)
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// A number in `0..n`. `n` must not be zero.
+    pub fn below(&mut self, n: usize) -> usize {
+        (self.next_u64() % n as u64) as usize
+    }
+
+    /// True with a chance of `percent` in 100.
+    pub fn chance(&mut self, percent: u64) -> bool {
+        self.next_u64() % 100 < percent
+    }
+
+    pub fn pick<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        &items[self.below(items.len())]
+    }
+}
+
+/// The timings of one generated input.
+#[derive(Clone, PartialEq, Debug)]
+pub struct Point {
+    /// The size the generator was asked for.
+    pub size: usize,
+    /// Length of the generated input.
+    pub bytes: usize,
+    pub parse: Option<Duration>,
+    pub parts: [Option<Duration>; 2],
+}
+
+impl Point {
+    pub fn elapsed(&self, step: Step) -> Option<Duration> {
+        match step {
+            Step::Parse => self.parse,
+            Step::Part(part) => self.parts[usize::from(part - 1)],
+        }
+    }
+}
+
+/// Runs `solve` on `data` `runs` times within `limits`, keeping the fastest time of every step.
+pub fn measure(
+    solve: Solve,
+    size: usize,
+    data: &str,
+    runs: usize,
+    limits: Limits,
+) -> Result<Point, (Step, Failure)> {
+    let mut ret = Point {
+        size,
+        bytes: data.len(),
+        parse: None,
+        parts: [None; 2],
+    };
+    for _ in 0..runs.max(1) {
+        limits::run(solve, data, limits, |event| {
+            if let Event::Finished { step, elapsed, .. } = event {
+                let best = match step {
+                    Step::Parse => &mut ret.parse,
+                    Step::Part(part) => &mut ret.parts[usize::from(part - 1)],
+                };
+                *best = Some(best.map_or(elapsed, |v| v.min(elapsed)));
+            }
+        })?;
+    }
+    Ok(ret)
+}
+
+/// The exponent `k` that best fits `time ~ bytes^k` for `step`, by least squares on the log-log
+/// timings. Only the larger half of the points is used, since fixed costs drown out the growth
+/// on small inputs. `None` with fewer than two usable points.
+pub fn growth(points: &[Point], step: Step) -> Option<f64> {
+    let logs: Vec<(f64, f64)> = points
+        .iter()
+        .filter_map(|point| {
+            let elapsed = point.elapsed(step)?.as_secs_f64();
+            (point.bytes > 0 && elapsed > 0.0).then(|| ((point.bytes as f64).ln(), elapsed.ln()))
+        })
+        .collect();
+    let larger = &logs[logs.len() / 2..];
+    fit_slope(if larger.len() >= 2 { larger } else { &logs })
+}
+
+fn fit_slope(logs: &[(f64, f64)]) -> Option<f64> {
+    if logs.len() < 2 {
+        return None;
+    }
+    let n = logs.len() as f64;
+    let mean_x = logs.iter().map(|v| v.0).sum::<f64>() / n;
+    let mean_y = logs.iter().map(|v| v.1).sum::<f64>() / n;
+    let covariance: f64 = logs.iter().map(|v| (v.0 - mean_x) * (v.1 - mean_y)).sum();
+    let variance: f64 = logs.iter().map(|v| (v.0 - mean_x).powi(2)).sum();
+    (variance > 0.0).then(|| covariance / variance)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn point(bytes: usize, micros: [u64; 2]) -> Point {
+        Point {
+            size: bytes,
+            bytes,
+            parse: None,
+            parts: micros.map(|v| Some(Duration::from_micros(v))),
+        }
+    }
+
+    #[test]
+    fn scale_growth() {
+        // Linear part 1, quadratic part 2, with noise in the smallest point
+        let points = [
+            point(10, [50, 50]),
+            point(100, [100, 1_000]),
+            point(200, [200, 4_000]),
+            point(400, [400, 16_000]),
+            point(800, [800, 64_000]),
+        ];
+        let close = |v: Option<f64>, expected: f64| v.is_some_and(|v| (v - expected).abs() < 1e-9);
+        assert!(close(growth(&points, Step::Part(1)), 1.0));
+        assert!(close(growth(&points, Step::Part(2)), 2.0));
+        assert_eq!(growth(&points, Step::Parse), None);
+        assert_eq!(growth(&points[..1], Step::Part(1)), None);
+    }
+
+    #[test]
+    fn scale_generators() {
+        let mut rng = Rng::new(1);
+        let first: Vec<_> = (0..4).map(|_| rng.next_u64()).collect();
+        let mut rng = Rng::new(1);
+        assert_eq!(first, (0..4).map(|_| rng.next_u64()).collect::<Vec<_>>());
+
+        for day in crate::days::all() {
+            let Some(generate) = day.generate else {
+                continue;
+            };
+            for size in [1, 8, 20] {
+                let data = generate(size, &mut Rng::new(size as u64));
+                let point = measure(day.solve, size, &data, 1, Limits::default());
+                assert!(point.is_ok(), "{day} size {size}: {point:?}\n{data}");
+            }
+        }
+    }
+}
@@ -0,0 +1,144 @@
+//! Golden snapshot tests for anything rendered as text.
+//!
+//! [`assert_snapshot`] compares against `snapshots/NAME.snap`. Run the tests with `AOC_BLESS=1` to
+//! write the current output instead, and review the changed files before committing them.
+
+use std::path::PathBuf;
+
+pub const BLESS_VAR: &str = "AOC_BLESS";
+
+/// Lines of unchanged context shown around every change in a diff.
+const CONTEXT: usize = 2;
+
+fn path(name: &str) -> PathBuf {
+    [
+        env!("CARGO_MANIFEST_DIR"),
+        "snapshots",
+        &format!("{name}.snap"),
+    ]
+    .iter()
+    .collect()
+}
+
+fn blessing() -> bool {
+    std::env::var(BLESS_VAR).is_ok_and(|v| !v.is_empty() && v != "0")
+}
+
+/// Panics with a line diff if `actual` doesn't match the snapshot called `name`.
+#[track_caller]
+pub fn assert_snapshot(name: &str, actual: &str) {
+    let path = path(name);
+    let expected = std::fs::read_to_string(&path).ok();
+    if expected.as_deref() == Some(actual) {
+        return;
+    }
+    if blessing() {
+        let write = std::fs::create_dir_all(path.parent().unwrap())
+            .and_then(|()| std::fs::write(&path, actual));
+        if let Err(e) = write {
+            panic!("cannot write {}: {e}", path.display());
+        }
+        return;
+    }
+    match expected {
+        Some(expected) => panic!(
+            "snapshot {name} does not match, rerun with {BLESS_VAR}=1 if this is expected\n{}",
+            line_diff(&expected, actual)
+        ),
+        None => panic!(
+            "no snapshot at {}, rerun with {BLESS_VAR}=1 to create it",
+            path.display()
+        ),
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+enum Line<'a> {
+    Same(&'a str),
+    Removed(&'a str),
+    Added(&'a str),
+}
+
+/// The longest common subsequence of lines, as a list of edits from `expected` to `actual`.
+fn edits<'a>(expected: &[&'a str], actual: &[&'a str]) -> Vec<Line<'a>> {
+    // lcs[i][j] is the LCS length of expected[i..] and actual[j..]
+    let mut lcs = vec![vec![0usize; actual.len() + 1]; expected.len() + 1];
+    for i in (0..expected.len()).rev() {
+        for j in (0..actual.len()).rev() {
+            lcs[i][j] = if expected[i] == actual[j] {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+
+    let mut ret = Vec::new();
+    let (mut i, mut j) = (0, 0);
+    while i < expected.len() || j < actual.len() {
+        if i < expected.len() && j < actual.len() && expected[i] == actual[j] {
+            ret.push(Line::Same(expected[i]));
+            (i, j) = (i + 1, j + 1);
+        } else if i < expected.len() && (j == actual.len() || lcs[i + 1][j] >= lcs[i][j + 1]) {
+            ret.push(Line::Removed(expected[i]));
+            i += 1;
+        } else {
+            ret.push(Line::Added(actual[j]));
+            j += 1;
+        }
+    }
+    ret
+}
+
+/// A diff of the lines of `expected` and `actual`, with `-` for expected and `+` for actual
+/// lines, and line numbers of `expected` on unchanged lines.
+pub fn line_diff(expected: &str, actual: &str) -> String {
+    let expected: Vec<_> = expected.lines().collect();
+    let actual: Vec<_> = actual.lines().collect();
+    let edits = edits(&expected, &actual);
+
+    let near_change = |i: usize| {
+        let range = i.saturating_sub(CONTEXT)..(i + CONTEXT + 1).min(edits.len());
+        edits[range].iter().any(|v| !matches!(v, Line::Same(_)))
+    };
+
+    let mut ret = String::new();
+    let mut line_no = 0;
+    let mut skipped = false;
+    for (i, edit) in edits.iter().enumerate() {
+        match edit {
+            Line::Same(line) => {
+                line_no += 1;
+                if near_change(i) {
+                    ret += &format!("{line_no:>4}  {line}\n");
+                    skipped = false;
+                } else if !skipped {
+                    ret += "   ...\n";
+                    skipped = true;
+                }
+            }
+            Line::Removed(line) => {
+                line_no += 1;
+                ret += &format!("{line_no:>4} -{line}\n");
+            }
+            Line::Added(line) => ret += &format!("     +{line}\n"),
+        }
+    }
+    ret
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn snapshot_line_diff() {
+        let expected = "a\nb\nc\nd\ne\nf\ng\n";
+        let actual = "a\nb\nc\nD\ne\nf\ng\nh\n";
+        assert_eq!(
+            line_diff(expected, actual),
+            "   ...\n   2  b\n   3  c\n   4 -d\n     +D\n   5  e\n   6  f\n   7  g\n     +h\n"
+        );
+        assert_eq!(line_diff("a\n", ""), "   1 -a\n");
+    }
+}
@@ -0,0 +1,203 @@
+//! Terminal rendering of grids, and frame-by-frame animation of solver state.
+//!
+//! Solvers call [`frame`] whenever they have something worth showing. Frames are dropped unless
+//! the runner enabled visualization with [`init`], in which case they are drawn on stderr and/or
+//! appended to a file that can be played back with [`replay`].
+
+use std::{
+    fmt::Write as _,
+    fs::File,
+    io::{BufWriter, Write},
+    path::Path,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Mutex,
+    },
+    time::Duration,
+};
+
+/// Separates frames in a frames file.
+const FRAME_SEPARATOR: &str = "\x0c\n";
+
+const CLEAR_SCREEN: &str = "\x1b[H\x1b[2J";
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Color {
+    Red,
+    Green,
+    Yellow,
+    Blue,
+    Magenta,
+    Cyan,
+    White,
+    Gray,
+}
+
+impl Color {
+    fn ansi(self) -> &'static str {
+        match self {
+            Self::Red => "\x1b[31m",
+            Self::Green => "\x1b[32m",
+            Self::Yellow => "\x1b[33m",
+            Self::Blue => "\x1b[34m",
+            Self::Magenta => "\x1b[35m",
+            Self::Cyan => "\x1b[36m",
+            Self::White => "\x1b[37m",
+            Self::Gray => "\x1b[90m",
+        }
+    }
+}
+
+const RESET: &str = "\x1b[0m";
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Glyph {
+    pub ch: char,
+    pub color: Option<Color>,
+}
+
+impl Glyph {
+    pub fn new(ch: char, color: Color) -> Self {
+        Self {
+            ch,
+            color: Some(color),
+        }
+    }
+}
+
+impl From<char> for Glyph {
+    fn from(ch: char) -> Self {
+        Self { ch, color: None }
+    }
+}
+
+/// Renders a `width` x `height` grid, asking `cell` for the glyph at every `(x, y)`. Colors are
+/// only emitted when `color` is set, so the plain output is suitable for comparing in tests.
+pub fn render(
+    width: usize,
+    height: usize,
+    color: bool,
+    mut cell: impl FnMut(usize, usize) -> Glyph,
+) -> String {
+    let mut ret = String::with_capacity((width + 1) * height);
+    for y in 0..height {
+        let mut cur_color = None;
+        for x in 0..width {
+            let glyph = cell(x, y);
+            if color && glyph.color != cur_color {
+                ret.push_str(glyph.color.map_or(RESET, Color::ansi));
+                cur_color = glyph.color;
+            }
+            ret.push(glyph.ch);
+        }
+        if cur_color.is_some() {
+            ret.push_str(RESET);
+        }
+        ret.push('\n');
+    }
+    ret
+}
+
+pub struct Config {
+    /// Draw frames on stderr.
+    pub terminal: bool,
+    /// Pause after every frame drawn on the terminal.
+    pub delay: Duration,
+    /// Append frames to this file.
+    pub frames: Option<File>,
+}
+
+struct Visualizer {
+    config: Config,
+    frames: Option<BufWriter<File>>,
+}
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+static VISUALIZER: Mutex<Option<Visualizer>> = Mutex::new(None);
+
+pub fn init(mut config: Config) {
+    let enabled = config.terminal || config.frames.is_some();
+    let frames = config.frames.take().map(BufWriter::new);
+    *VISUALIZER.lock().unwrap() = Some(Visualizer { config, frames });
+    ENABLED.store(enabled, Ordering::Relaxed);
+}
+
+/// Flushes the frames file, if any.
+pub fn finish() -> std::io::Result<()> {
+    ENABLED.store(false, Ordering::Relaxed);
+    match VISUALIZER.lock().unwrap().take() {
+        Some(Visualizer {
+            frames: Some(mut frames),
+            ..
+        }) => frames.flush(),
+        _ => Ok(()),
+    }
+}
+
+/// Whether frames are being shown. Solvers should check this before doing any work that is only
+/// needed for visualization.
+pub fn enabled() -> bool {
+    ENABLED.load(Ordering::Relaxed)
+}
+
+/// Renders and shows a single frame, with `title` on the line above the grid.
+pub fn frame(title: &str, width: usize, height: usize, cell: impl FnMut(usize, usize) -> Glyph) {
+    if !enabled() {
+        return;
+    }
+    let mut text = String::new();
+    let _ = writeln!(text, "{title}");
+    text.push_str(&render(width, height, true, cell));
+
+    let mut vis = VISUALIZER.lock().unwrap();
+    let Some(vis) = vis.as_mut() else {
+        return;
+    };
+    if let Some(frames) = &mut vis.frames {
+        // Visualization is best-effort, a full disk should not abort the solver
+        let _ = write!(frames, "{text}{FRAME_SEPARATOR}");
+    }
+    if vis.config.terminal {
+        eprint!("{CLEAR_SCREEN}{text}");
+        std::thread::sleep(vis.config.delay);
+    }
+}
+
+/// Plays back a frames file written by a previous run.
+pub fn replay(path: impl AsRef<Path>, delay: Duration) -> std::io::Result<()> {
+    let data = std::fs::read_to_string(path)?;
+    for frame in data.split(FRAME_SEPARATOR).filter(|v| !v.is_empty()) {
+        eprint!("{CLEAR_SCREEN}{frame}");
+        std::thread::sleep(delay);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn vis_render_plain() {
+        let res = render(3, 2, false, |x, y| {
+            if x == y {
+                Glyph::new('#', Color::Red)
+            } else {
+                '.'.into()
+            }
+        });
+        assert_eq!(res, "#..\n.#.\n");
+    }
+
+    #[test]
+    fn vis_render_color() {
+        let res = render(3, 1, true, |x, _| {
+            if x < 2 {
+                Glyph::new('#', Color::Red)
+            } else {
+                '.'.into()
+            }
+        });
+        assert_eq!(res, "\x1b[31m##\x1b[0m.\n");
+    }
+}
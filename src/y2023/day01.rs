@@ -0,0 +1,136 @@
+use crate::{explain::Table, scale::Rng};
+
+pub const TITLE: &str = "Trebuchet?!";
+
+fn digits(line: &str) -> impl DoubleEndedIterator<Item = u64> + Clone + '_ {
+    line.as_bytes()
+        .iter()
+        .copied()
+        .filter(|b| b.is_ascii_digit())
+        .map(|b| u64::from(b - b'0'))
+}
+
+/// Digits, including the ones spelled out.
+fn spelled_digits(line: &str) -> impl DoubleEndedIterator<Item = usize> + Clone + '_ {
+    static NUMBERS: [&str; 9] = [
+        "one", "two", "three", "four", "five", "six", "seven", "eight", "nine",
+    ];
+    line.as_bytes()
+        .iter()
+        .copied()
+        .enumerate()
+        .filter_map(move |(i, b)| {
+            if b.is_ascii_digit() {
+                Some(usize::from(b - b'0'))
+            } else {
+                NUMBERS
+                    .iter()
+                    .position(|num| line.as_bytes()[i..].starts_with(num.as_bytes()))
+                    .map(|x| x + 1)
+            }
+        })
+}
+
+pub fn part1(data: &str) -> u64 {
+    data.trim()
+        .lines()
+        .map(|l| {
+            let mut it = digits(l);
+            (it.clone().next().unwrap(), it.next_back().unwrap())
+        })
+        .map(|(a, b)| a * 10 + b)
+        .sum()
+}
+
+pub fn part2(data: &str) -> usize {
+    data.trim()
+        .lines()
+        .map(|l| {
+            let mut it = spelled_digits(l);
+            (it.clone().next().unwrap(), it.next_back().unwrap())
+        })
+        .map(|(a, b)| a * 10 + b)
+        .sum()
+}
+
+pub fn explain(data: &str, part: u8) -> Table {
+    let mut ret = Table::new(&["line", "digits", "calibration"]);
+    for line in data.trim().lines() {
+        let found: Vec<u64> = if part == 1 {
+            digits(line).collect()
+        } else {
+            spelled_digits(line).map(|v| v as u64).collect()
+        };
+        let calibration = found.first().unwrap() * 10 + found.last().unwrap();
+        ret.row([line.into(), found.into(), calibration.into()]);
+    }
+    ret
+}
+
+pub fn generate(lines: usize, rng: &mut Rng) -> String {
+    const WORDS: [&str; 6] = ["one", "four", "seven", "eight", "nine", "xtwone"];
+    let mut ret = String::new();
+    for _ in 0..lines {
+        let mut line = String::new();
+        let digit_at = rng.below(6);
+        for i in 0..6 {
+            if i == digit_at {
+                line.push(char::from(b'1' + rng.below(9) as u8));
+            } else if rng.chance(30) {
+                line += *rng.pick(&WORDS);
+            } else {
+                line.push(char::from(b'a' + rng.below(26) as u8));
+            }
+        }
+        ret += &line;
+        ret.push('\n');
+    }
+    ret
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PART1: &str = r#"
+1abc2
+pqr3stu8vwx
+a1b2c3d4e5f
+treb7uchet"#;
+    const PART2: &str = r#"
+two1nine
+eightwothree
+abcone2threexyz
+xtwone3four
+4nineeightseven2
+zoneight234
+7pqrstsixteen"#;
+
+    #[test]
+    fn day01_part1() {
+        assert_eq!(part1(PART1), 142);
+    }
+
+    #[test]
+    fn day01_part2() {
+        assert_eq!(part2(PART2), 281);
+    }
+
+    #[test]
+    fn day01_explain() {
+        let table = explain(PART2, 2);
+        assert_eq!(table.rows().len(), 7);
+        assert_eq!(
+            table.rows()[3],
+            [
+                "xtwone3four".into(),
+                vec![2u64, 1, 3, 4].into(),
+                24u64.into()
+            ]
+        );
+        assert_eq!(
+            explain(PART1, 1).text().lines().nth(3),
+            Some("a1b2c3d4e5f  1 2 3 4 5           15")
+        );
+    }
+}
@@ -1,3 +1,5 @@
+pub const TITLE: &str = "Cube Conundrum";
+
 #[derive(Default, Clone)]
 struct GameSet {
     red: usize,
@@ -30,7 +32,13 @@ fn parse_game(line: &str) -> (usize, impl Iterator<Item = GameSet> + '_) {
     )
 }
 
-fn part1(data: &str) -> usize {
+pub fn validate(data: &str) {
+    for line in data.trim().lines() {
+        parse_game(line).1.for_each(drop);
+    }
+}
+
+pub fn part1(data: &str) -> usize {
     let bag = GameSet {
         red: 12,
         green: 13,
@@ -51,10 +59,10 @@ fn part1(data: &str) -> usize {
         .sum()
 }
 
-fn part2(data: &str) -> usize {
+pub fn part2(data: &str) -> usize {
     data.trim()
         .lines()
-        .filter_map(|l| -> Option<usize> {
+        .map(|l| -> usize {
             let (_, sets) = parse_game(l);
             let mut min_game = GameSet::default();
             for GameSet { red, green, blue } in sets {
@@ -63,18 +71,11 @@ fn part2(data: &str) -> usize {
                 min_game.blue = blue.max(min_game.blue);
             }
 
-            Some(min_game.red * min_game.green * min_game.blue)
+            min_game.red * min_game.green * min_game.blue
         })
         .sum()
 }
 
-fn main() {
-    println!("Day 2");
-    let data = std::fs::read_to_string("input/day02.txt").unwrap();
-    println!("Part 1: {}", part1(&data));
-    println!("Part 2: {}", part2(&data));
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1,3 +1,5 @@
+pub const TITLE: &str = "Gear Ratios";
+
 #[derive(Debug)]
 enum Cell {
     Num { digits: u8, value: u16 },
@@ -16,7 +18,7 @@ impl Cell {
     }
 }
 
-struct Grid {
+pub struct Grid {
     grid: Vec<Vec<Cell>>,
 }
 
@@ -66,8 +68,11 @@ impl Grid {
     }
 }
 
-fn part1(data: &str) -> usize {
-    let grid = Grid::from_input(data);
+pub fn parse(data: &str) -> Grid {
+    Grid::from_input(data)
+}
+
+pub fn part1(grid: &Grid) -> usize {
     let grid = grid.grid.as_slice();
 
     grid.iter()
@@ -79,8 +84,8 @@ fn part1(data: &str) -> usize {
                     value: value @ 1..,
                 } => {
                     let range = x.saturating_sub(usize::from(digits))..(x + 2).min(row.len());
-                    for y in y.saturating_sub(1)..(y + 2).min(grid.len()) {
-                        if grid[y][range.clone()].iter().any(|v| v.is_part()) {
+                    for row in &grid[y.saturating_sub(1)..(y + 2).min(grid.len())] {
+                        if row[range.clone()].iter().any(|v| v.is_part()) {
                             return value;
                         }
                     }
@@ -93,8 +98,7 @@ fn part1(data: &str) -> usize {
         .sum()
 }
 
-fn part2(data: &str) -> usize {
-    let grid = Grid::from_input(data);
+pub fn part2(grid: &Grid) -> usize {
     let grid = grid.grid.as_slice();
 
     grid.iter()
@@ -104,9 +108,9 @@ fn part2(data: &str) -> usize {
                 Cell::Gear => {
                     let range = x.saturating_sub(1)..(x + 2).min(row.len());
                     let mut nums = Vec::with_capacity(2);
-                    for y in y.saturating_sub(1)..(y + 2).min(grid.len()) {
+                    for row in &grid[y.saturating_sub(1)..(y + 2).min(grid.len())] {
                         let mut last_was_numpart = true;
-                        for cell in grid[y][range.clone()].iter() {
+                        for cell in row[range.clone()].iter() {
                             last_was_numpart = false;
                             match *cell {
                                 Cell::Num { value, .. } => nums.push(usize::from(value)),
@@ -115,7 +119,7 @@ fn part2(data: &str) -> usize {
                             }
                         }
                         if last_was_numpart {
-                            'get_num: for cell in &grid[y][range.end..] {
+                            'get_num: for cell in &row[range.end..] {
                                 match cell {
                                     &Cell::Num { value, .. } => {
                                         nums.push(usize::from(value));
@@ -139,13 +143,6 @@ fn part2(data: &str) -> usize {
         .sum()
 }
 
-fn main() {
-    println!("Day 3");
-    let data = std::fs::read_to_string("input/day03.txt").unwrap();
-    println!("Part 1: {}", part1(&data));
-    println!("Part 2: {}", part2(&data));
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -164,11 +161,11 @@ mod tests {
 
     #[test]
     fn day03_part1() {
-        assert_eq!(part1(PART1), 4361);
+        assert_eq!(part1(&parse(PART1)), 4361);
     }
 
     #[test]
     fn day03_part2() {
-        assert_eq!(part2(PART1), 467835);
+        assert_eq!(part2(&parse(PART1)), 467835);
     }
 }
@@ -0,0 +1,173 @@
+use crate::{
+    arith,
+    explain::Table,
+    hash::FastSet,
+    parse::{self, ParseError},
+};
+
+pub const TITLE: &str = "Scratchcards";
+
+struct Set {
+    num: FastSet<usize>,
+}
+
+impl Set {
+    fn from_input(input: &str) -> Result<Self, ParseError<'_>> {
+        Ok(Self {
+            num: parse::columns(input, 3)?
+                .into_iter()
+                .map(parse::integer)
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+struct Card {
+    win: Set,
+    your: Set,
+}
+
+impl Card {
+    fn from_line(line: &str) -> Result<Self, ParseError<'_>> {
+        let numbers = parse::key_value(line)?.1;
+        let (win, your) = numbers.split_once(" | ").ok_or_else(|| ParseError {
+            at: numbers,
+            expected: "`winning | yours`".to_owned(),
+        })?;
+        Ok(Self {
+            win: Set::from_input(win)?,
+            your: Set::from_input(your)?,
+        })
+    }
+
+    fn winners(&self) -> Vec<usize> {
+        let mut ret: Vec<_> = self.win.num.intersection(&self.your.num).copied().collect();
+        ret.sort_unstable();
+        ret
+    }
+}
+
+/// The cards of the input, panicking with the position of the first that doesn't parse.
+fn cards(data: &str) -> impl Iterator<Item = Card> + '_ {
+    data.trim().lines().map(|line| {
+        Card::from_line(line).unwrap_or_else(|err| match err.position(data) {
+            Some(position) => panic!("{err} at {position}"),
+            None => panic!("{err}"),
+        })
+    })
+}
+
+/// Your winning numbers on every card, which both the parts and the explanation count.
+fn winning_numbers(data: &str) -> Vec<Vec<usize>> {
+    cards(data).map(|card| card.winners()).collect()
+}
+
+/// The number of winning numbers on every card.
+pub fn parse(data: &str) -> Vec<usize> {
+    winning_numbers(data).iter().map(Vec::len).collect()
+}
+
+fn points(count: usize) -> usize {
+    if count > 0 {
+        arith::shl(1, (count - 1) as u32)
+    } else {
+        0
+    }
+}
+
+pub fn part1(winners: &[usize]) -> usize {
+    arith::sum(winners.iter().map(|&count| points(count)))
+}
+
+/// How many of every card there are in the end.
+fn copies(winners: &[usize]) -> Vec<usize> {
+    struct Entry {
+        value: usize,
+        count: usize,
+    }
+
+    let mut sums: Vec<Entry> = winners
+        .iter()
+        .map(|&value| Entry { value, count: 1 })
+        .collect();
+
+    for i in 0..sums.len() {
+        let (cur, remain) = sums[i..].split_first_mut().unwrap();
+        for x in &mut remain[..cur.value] {
+            x.count = arith::add(x.count, cur.count);
+        }
+    }
+
+    sums.iter().map(|v| v.count).collect()
+}
+
+pub fn part2(winners: &[usize]) -> usize {
+    arith::sum(copies(winners))
+}
+
+pub fn explain(data: &str, part: u8) -> Table {
+    let cards = winning_numbers(data);
+    let counts: Vec<_> = cards.iter().map(Vec::len).collect();
+    let copies = copies(&counts);
+    let mut ret = Table::new(&[
+        "card",
+        "winners",
+        if part == 1 { "points" } else { "copies" },
+    ]);
+    for (i, winners) in cards.into_iter().enumerate() {
+        let value = if part == 1 {
+            points(winners.len())
+        } else {
+            copies[i]
+        };
+        ret.row([(i + 1).into(), winners.into(), value.into()]);
+    }
+    ret
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PART1: &str = r#"
+Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53
+Card 2: 13 32 20 16 61 | 61 30 68 82 17 32 24 19
+Card 3:  1 21 53 59 44 | 69 82 63 72 16 21 14  1
+Card 4: 41 92 73 84 69 | 59 84 76 51 58  5 54 83
+Card 5: 87 83 26 28 32 | 88 30 70 12 93 22 82 36
+Card 6: 31 18 13 56 72 | 74 77 10 23 35 67 36 11"#;
+
+    #[test]
+    fn day04_part1() {
+        assert_eq!(part1(&parse(PART1)), 13);
+    }
+
+    #[test]
+    fn day04_parse_error() {
+        let data = "Card 1: 41 48 | 83 86\nCard 2: 13 2x | 61 30";
+        let err = crate::inspect::validate(|data| drop(parse(data)), data).unwrap_err();
+        assert!(
+            err.starts_with("expected whitespace, found \"x\" at line 2, column 13"),
+            "{err}"
+        );
+    }
+
+    #[test]
+    fn day04_part2() {
+        assert_eq!(part2(&parse(PART1)), 30);
+    }
+
+    #[test]
+    fn day04_explain() {
+        assert_eq!(
+            explain(PART1, 1).rows()[0],
+            [1.into(), vec![17, 48, 83, 86].into(), 8.into()]
+        );
+        let copies: Vec<_> = explain(PART1, 2)
+            .rows()
+            .iter()
+            .map(|row| row[2].clone())
+            .collect();
+        assert_eq!(copies, [1, 2, 4, 8, 14, 1].map(Into::into));
+    }
+}
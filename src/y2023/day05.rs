@@ -0,0 +1,303 @@
+use std::ops::Range;
+
+use crate::{arith, debug, hash::FastMap, parse, range::RangeSet, repl, trace};
+
+pub const TITLE: &str = "If You Give A Seed A Fertilizer";
+
+pub struct Almanac<'a> {
+    seeds: Vec<usize>,
+    maps: FastMap<&'a str, Map<'a>>,
+}
+
+impl<'a> Almanac<'a> {
+    fn from_input(input: &'a str) -> Self {
+        let mut sections = parse::sections(input);
+        let seeds = parse::prefixed_integers(sections.next().unwrap(), "seeds: ").unwrap();
+
+        let mut maps = FastMap::default();
+
+        for section in sections {
+            let mut lines = section.lines();
+            let mut map = Map::from_input(lines.next().unwrap());
+            map.entries = lines.map(MapEntry::from_input).collect();
+            let prev = maps.insert(map.from, map);
+            assert!(prev.is_none());
+        }
+
+        maps.values_mut()
+            .for_each(|v| v.entries.sort_by_key(|e| e.src_start));
+
+        Self { seeds, maps }
+    }
+}
+
+struct Map<'a> {
+    from: &'a str,
+    to: &'a str,
+    entries: Vec<MapEntry>,
+}
+
+impl<'a> Map<'a> {
+    fn from_input(input: &'a str) -> Self {
+        let (from, remain) = input.split_once("-to-").unwrap();
+        let (to, remain) = remain.split_once(' ').unwrap();
+        assert_eq!(remain, "map:");
+        Self {
+            from,
+            to,
+            entries: Vec::new(),
+        }
+    }
+
+    /// Where `value` and the values after it map to contiguously, and whether `value` is in
+    /// one of the entries rather than mapping to itself.
+    fn map_entry(&self, value: usize) -> (Range<usize>, bool) {
+        match self.entries.binary_search_by_key(&value, |v| v.src_start) {
+            Ok(i) => {
+                let entry = &self.entries[i];
+                (
+                    entry.dst_start..arith::add(entry.dst_start, entry.len),
+                    true,
+                )
+            }
+            Err(i) => {
+                if i == 0 {
+                    (value..self.entries[0].src_start, false)
+                } else {
+                    let entry = &self.entries[i - 1];
+                    let offset = value - entry.src_start;
+                    if offset < entry.len {
+                        let start = arith::add(entry.dst_start, offset);
+                        (start..arith::add(entry.dst_start, entry.len), true)
+                    } else if let Some(next) = self.entries.get(i) {
+                        (value..next.src_start, false)
+                    } else {
+                        (value..usize::MAX, false)
+                    }
+                }
+            }
+        }
+    }
+
+    /// Maps every value in `set`. Values outside the entries map to themselves.
+    fn map_set(&self, set: &RangeSet<usize>) -> RangeSet<usize> {
+        let mut unmapped = set.clone();
+        let mut ret = RangeSet::new();
+        for entry in &self.entries {
+            let src = entry.src_start..arith::add(entry.src_start, entry.len);
+            let mapped = set.intersection(&src.clone().into());
+            ret.extend(mapped.shift(entry.src_start, entry.dst_start).iter());
+            unmapped.remove(src);
+        }
+        ret.union(&unmapped)
+    }
+}
+
+struct MapEntry {
+    dst_start: usize,
+    src_start: usize,
+    len: usize,
+}
+
+impl MapEntry {
+    fn from_input(input: &str) -> Self {
+        let [dst_start, src_start, len] = parse::integer_array(input).unwrap();
+        Self {
+            dst_start,
+            src_start,
+            len,
+        }
+    }
+}
+
+pub fn parse(data: &str) -> Almanac<'_> {
+    Almanac::from_input(data)
+}
+
+pub fn part1(almanac: &Almanac) -> usize {
+    almanac
+        .seeds
+        .iter()
+        .map(|&seed| {
+            let mut map = &almanac.maps["seed"];
+            let mut cur = seed;
+            loop {
+                let next = map.map_entry(cur).0.start;
+                trace!("{} {cur} -> {} {next}", map.from, map.to);
+                cur = next;
+                if map.to == "location" {
+                    debug!("seed {seed} -> location {cur}");
+                    return cur;
+                }
+                map = &almanac.maps[map.to];
+            }
+        })
+        .min()
+        .unwrap()
+}
+
+pub fn part2(almanac: &Almanac) -> usize {
+    let mut cur: RangeSet<usize> = almanac
+        .seeds
+        .chunks(2)
+        .map(|range| range[0]..arith::add(range[0], range[1]))
+        .collect();
+    let mut map = &almanac.maps["seed"];
+    loop {
+        let next = map.map_set(&cur);
+        trace!("{} {cur:?} -> {} {next:?}", map.from, map.to);
+        cur = next;
+        if map.to == "location" {
+            break;
+        }
+        map = &almanac.maps[map.to];
+    }
+    debug!("{} location ranges", cur.iter().count());
+    let lowest = cur.iter().next().unwrap();
+    lowest.start
+}
+
+pub fn repl(data: &str) -> Box<dyn repl::Session + '_> {
+    Box::new(
+        repl::Commands::new(parse(data))
+            .command("seeds", "List the seed numbers", |almanac, _| {
+                Ok(format!("{:?}", almanac.seeds))
+            })
+            .command("maps", "List the maps", |almanac, _| {
+                let mut map = almanac.maps.get("seed").ok_or("no seed map")?;
+                let mut ret = String::new();
+                loop {
+                    ret += &format!(
+                        "{}-to-{}: {} entries\n",
+                        map.from,
+                        map.to,
+                        map.entries.len()
+                    );
+                    match almanac.maps.get(map.to) {
+                        Some(next) => map = next,
+                        None => return Ok(ret),
+                    }
+                }
+            })
+            .command(
+                "map VALUE [FROM]",
+                "Follow VALUE from category FROM (default seed) through all maps",
+                |almanac, args| {
+                    let mut cur: usize = args
+                        .first()
+                        .and_then(|v| v.parse().ok())
+                        .ok_or("expected a number")?;
+                    let mut category = args.get(1).copied().unwrap_or("seed");
+                    let mut map = almanac
+                        .maps
+                        .get(category)
+                        .ok_or_else(|| format!("no map from {category:?}"))?;
+                    let mut ret = format!("{category} {cur}");
+                    loop {
+                        let (range, remapped) = map.map_entry(cur);
+                        cur = range.start;
+                        category = map.to;
+                        if !remapped {
+                            ret += &format!("\n -> {category} {cur} (not remapped by this map)");
+                        } else {
+                            ret += &format!("\n -> {category} {cur} ({} contiguous)", range.len());
+                        }
+                        match almanac.maps.get(category) {
+                            Some(next) => map = next,
+                            None => return Ok(ret),
+                        }
+                    }
+                },
+            ),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PART1: &str = r#"
+seeds: 79 14 55 13
+
+seed-to-soil map:
+50 98 2
+52 50 48
+
+soil-to-fertilizer map:
+0 15 37
+37 52 2
+39 0 15
+
+fertilizer-to-water map:
+49 53 8
+0 11 42
+42 0 7
+57 7 4
+
+water-to-light map:
+88 18 7
+18 25 70
+
+light-to-temperature map:
+45 77 23
+81 45 19
+68 64 13
+
+temperature-to-humidity map:
+0 69 1
+1 0 69
+
+humidity-to-location map:
+60 56 37
+56 93 4"#;
+
+    #[test]
+    fn day05_part1() {
+        assert_eq!(part1(&parse(PART1)), 35);
+    }
+
+    #[test]
+    fn day05_part2() {
+        assert_eq!(part2(&parse(PART1)), 46);
+    }
+
+    #[test]
+    fn day05_repl() {
+        let session = repl(PART1);
+        assert_eq!(
+            session.execute("map", &["79"]).unwrap().unwrap(),
+            "seed 79\n \
+             -> soil 81 (19 contiguous)\n \
+             -> fertilizer 81 (not remapped by this map)\n \
+             -> water 81 (not remapped by this map)\n \
+             -> light 74 (14 contiguous)\n \
+             -> temperature 78 (3 contiguous)\n \
+             -> humidity 78 (not remapped by this map)\n \
+             -> location 82 (15 contiguous)"
+        );
+        let res = session.execute("map", &["81", "soil"]).unwrap().unwrap();
+        assert!(res.ends_with(" -> location 82 (15 contiguous)"), "{res}");
+        assert!(session.execute("map", &["1", "nope"]).unwrap().is_err());
+    }
+
+    #[test]
+    fn day05_repl_identity() {
+        // Below the first entry
+        let res = repl(PART1).execute("map", &["10"]).unwrap().unwrap();
+        assert!(
+            res.starts_with("seed 10\n -> soil 10 (not remapped by this map)\n"),
+            "{res}"
+        );
+
+        // Between two entries
+        let session = repl("seeds: 1\n\nseed-to-soil map:\n10 0 5\n20 10 5");
+        assert_eq!(
+            session.execute("map", &["7"]).unwrap().unwrap(),
+            "seed 7\n -> soil 7 (not remapped by this map)"
+        );
+        assert_eq!(
+            session.execute("map", &["2"]).unwrap().unwrap(),
+            "seed 2\n -> soil 12 (3 contiguous)"
+        );
+    }
+}
@@ -0,0 +1,116 @@
+use crate::{arith, math, parse};
+
+pub const TITLE: &str = "Wait For It";
+
+struct Race {
+    time: usize,
+    dist: usize,
+}
+
+impl Race {
+    /// Number of hold times that beat the record. The distance is symmetric around `time / 2`,
+    /// so it's enough to find the first winning hold time.
+    fn count_wins(&self) -> usize {
+        let (time, dist) = (self.time as u64, self.dist as u64);
+        // hold^2 - time * hold + dist < 0 between the roots (time +- sqrt(time^2 - 4 * dist)) / 2
+        let Some(discriminant) = arith::mul(time, time).checked_sub(arith::mul(4, dist)) else {
+            return 0;
+        };
+        let beats = |hold: u64| arith::mul(hold, time - hold) > dist;
+
+        // The square root is rounded down, so the estimate can be off by one either way
+        let mut first = (time - math::isqrt_u64(discriminant)) / 2;
+        while first <= time / 2 && !beats(first) {
+            first += 1;
+        }
+        while first > 0 && beats(first - 1) {
+            first -= 1;
+        }
+        if first > time / 2 {
+            return 0;
+        }
+        (time - (2 * first) + 1) as usize
+    }
+}
+
+fn parse_line(line: &str, prefix: &str) -> Vec<usize> {
+    parse::prefixed_integers(line, prefix).unwrap()
+}
+
+fn parse_line2(line: &str, prefix: &str) -> usize {
+    let digits = parse::prefixed(line, prefix).unwrap().replace(' ', "");
+    parse::integer(&digits).unwrap()
+}
+
+fn parse_races(data: &str) -> impl Iterator<Item = Race> + '_ {
+    let mut lines = data.trim().lines();
+    let time = parse_line(lines.next().unwrap(), "Time:");
+    let dist = parse_line(lines.next().unwrap(), "Distance:");
+    assert_eq!(lines.next(), None);
+
+    assert_eq!(time.len(), dist.len());
+
+    time.into_iter()
+        .zip(dist)
+        .map(|(time, dist)| Race { time, dist })
+}
+
+/// The single race of part 2, ignoring the spaces between the digits.
+fn parse_race(data: &str) -> Race {
+    let mut lines = data.trim().lines();
+    let time = parse_line2(lines.next().unwrap(), "Time:");
+    let dist = parse_line2(lines.next().unwrap(), "Distance:");
+
+    Race { time, dist }
+}
+
+pub fn validate(data: &str) {
+    parse_races(data).for_each(drop);
+    parse_race(data);
+}
+
+pub fn part1(data: &str) -> usize {
+    arith::product(parse_races(data).map(|race| race.count_wins()))
+}
+
+pub fn part2(data: &str) -> usize {
+    parse_race(data).count_wins()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PART1: &str = r#"
+Time:      7  15   30
+Distance:  9  40  200"#;
+
+    #[test]
+    fn day06_part1() {
+        assert_eq!(part1(PART1), 288);
+    }
+
+    #[test]
+    fn day06_count_wins() {
+        for time in 0..40 {
+            for dist in 0..time * time {
+                let expected = (0..=time)
+                    .filter(|hold| hold * (time - hold) > dist)
+                    .count();
+                assert_eq!(Race { time, dist }.count_wins(), expected, "{time} {dist}");
+            }
+        }
+    }
+
+    #[test]
+    fn day06_part2() {
+        assert_eq!(part2(PART1), 71503);
+    }
+
+    #[test]
+    fn day06_validate() {
+        validate(PART1);
+        let err = crate::inspect::validate(validate, "Time: 7 15\nDistance: 9");
+        assert!(err.is_err(), "{err:?}");
+    }
+}
@@ -0,0 +1,289 @@
+use crate::{explain::Table, repl};
+
+pub const TITLE: &str = "Camel Cards";
+
+#[derive(Clone, Copy, PartialOrd, Ord, PartialEq, Eq, Debug)]
+struct Card(u8);
+
+static CARDS: [Card; 256] = {
+    let mut val = [Card(0); 256];
+    val[b'2' as usize] = Card(2);
+    val[b'3' as usize] = Card(3);
+    val[b'4' as usize] = Card(4);
+    val[b'5' as usize] = Card(5);
+    val[b'6' as usize] = Card(6);
+    val[b'7' as usize] = Card(7);
+    val[b'8' as usize] = Card(8);
+    val[b'9' as usize] = Card(9);
+    val[b'T' as usize] = Card(10);
+    val[b'J' as usize] = Card(11);
+    val[b'Q' as usize] = Card(12);
+    val[b'K' as usize] = Card(13);
+    val[b'A' as usize] = Card(14);
+    val
+};
+
+static CARDS2: [Card; 256] = {
+    let mut val = CARDS;
+    val[b'J' as usize] = Card(1);
+    val
+};
+
+fn calc_score(mut cards: [Card; 5]) -> usize {
+    cards.sort();
+
+    let mut counts = [0; 6];
+    {
+        let mut it = cards.into_iter().filter(|&c| c != Card(1));
+        if let Some(mut prev_card) = it.next() {
+            let mut count = 1;
+
+            for card in it {
+                if card == prev_card {
+                    count += 1;
+                } else {
+                    counts[count] += 1;
+                    count = 1;
+                }
+                prev_card = card;
+            }
+
+            counts[count] += 1;
+        }
+    }
+    let jokers = cards.into_iter().filter(|&c| c == Card(1)).count();
+
+    if let Some(i) = counts.iter().rposition(|&c| c > 0) {
+        counts[i] -= 1;
+        counts[i + jokers] += 1;
+    } else {
+        // All jokers
+        counts[5] += 1;
+    }
+
+    if counts[5] == 1 {
+        7
+    } else if counts[4] == 1 {
+        6
+    } else if counts[3] == 1 && counts[2] == 1 {
+        5
+    } else if counts[3] == 1 {
+        4
+    } else if counts[2] == 2 {
+        3
+    } else if counts[2] == 1 {
+        2
+    } else {
+        1
+    }
+}
+
+#[derive(PartialOrd, Ord, PartialEq, Eq, Debug)]
+struct Hand {
+    score: usize,
+    cards: [Card; 5],
+    bet: usize,
+}
+
+impl Hand {
+    fn from_input(input: &str, card_map: &[Card; 256]) -> Self {
+        let (hand, bet) = input.split_once(' ').unwrap();
+        let cards = <[u8; 5]>::try_from(hand.as_bytes())
+            .unwrap()
+            .map(|v| card_map[v as usize]);
+
+        let score = calc_score(cards);
+
+        Self {
+            score,
+            cards,
+            bet: bet.parse().unwrap(),
+        }
+    }
+}
+
+fn calc_card_winnings(data: &str, card_map: &[Card; 256]) -> usize {
+    let mut hands: Vec<_> = data
+        .trim()
+        .lines()
+        .map(|v| Hand::from_input(v, card_map))
+        .collect();
+    hands.sort();
+    hands
+        .iter()
+        .enumerate()
+        .map(|(i, hand)| hand.bet * (i + 1))
+        .sum()
+}
+
+pub fn validate(data: &str) {
+    for line in data.trim().lines() {
+        let (hand, bet) = line.split_once(' ').unwrap();
+        parse_cards(hand, &CARDS).unwrap();
+        bet.parse::<usize>().unwrap();
+    }
+}
+
+pub fn part1(data: &str) -> usize {
+    calc_card_winnings(data, &CARDS)
+}
+
+pub fn part2(data: &str) -> usize {
+    calc_card_winnings(data, &CARDS2)
+}
+
+fn score_name(score: usize) -> &'static str {
+    match score {
+        7 => "five of a kind",
+        6 => "four of a kind",
+        5 => "full house",
+        4 => "three of a kind",
+        3 => "two pair",
+        2 => "one pair",
+        _ => "high card",
+    }
+}
+
+fn parse_cards(hand: &str, card_map: &[Card; 256]) -> Result<[Card; 5], String> {
+    let cards = <[u8; 5]>::try_from(hand.as_bytes())
+        .map_err(|_| format!("{hand:?} is not 5 cards"))?
+        .map(|v| card_map[v as usize]);
+    if cards.contains(&Card(0)) {
+        return Err(format!("{hand:?} contains an unknown card"));
+    }
+    Ok(cards)
+}
+
+pub fn explain(data: &str, part: u8) -> Table {
+    let card_map = if part == 1 { &CARDS } else { &CARDS2 };
+    let mut hands: Vec<_> = data
+        .trim()
+        .lines()
+        .map(|v| (Hand::from_input(v, card_map), v.split_once(' ').unwrap().0))
+        .collect();
+    hands.sort();
+    let mut ret = Table::new(&["hand", "type", "rank", "bid", "winnings"]);
+    for (i, (hand, cards)) in hands.iter().enumerate() {
+        ret.row([
+            (*cards).into(),
+            score_name(hand.score).into(),
+            (i + 1).into(),
+            hand.bet.into(),
+            (hand.bet * (i + 1)).into(),
+        ]);
+    }
+    ret
+}
+
+pub fn repl(data: &str) -> Box<dyn repl::Session + '_> {
+    // The input hands ranked for both parts
+    let ranked = [&CARDS, &CARDS2].map(|card_map| {
+        let mut hands: Vec<_> = data
+            .trim()
+            .lines()
+            .map(|v| Hand::from_input(v, card_map))
+            .collect();
+        hands.sort();
+        hands
+    });
+    Box::new(
+        repl::Commands::new(ranked)
+            .command(
+                "hand CARDS",
+                "Hand type of CARDS with and without jokers",
+                |_, args| {
+                    let hand = args.first().ok_or("expected cards")?;
+                    let normal = calc_score(parse_cards(hand, &CARDS)?);
+                    let jokers = calc_score(parse_cards(hand, &CARDS2)?);
+                    Ok(format!(
+                        "part 1: {}\npart 2: {}",
+                        score_name(normal),
+                        score_name(jokers)
+                    ))
+                },
+            )
+            .command(
+                "rank CARDS",
+                "Type, rank and winnings of the input hand CARDS in both parts",
+                |ranked, args| {
+                    let hand = args.first().ok_or("expected cards")?;
+                    let mut ret = String::new();
+                    for (part, card_map, hands) in
+                        [(1, &CARDS, &ranked[0]), (2, &CARDS2, &ranked[1])]
+                    {
+                        let cards = parse_cards(hand, card_map)?;
+                        let rank = hands
+                            .iter()
+                            .position(|v| v.cards == cards)
+                            .ok_or_else(|| format!("{hand:?} is not in the input"))?;
+                        ret += &format!(
+                            "part {part}: {}, rank {} of {}, bet {} wins {}\n",
+                            score_name(hands[rank].score),
+                            rank + 1,
+                            hands.len(),
+                            hands[rank].bet,
+                            hands[rank].bet * (rank + 1)
+                        );
+                    }
+                    Ok(ret)
+                },
+            ),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PART1: &str = r#"
+32T3K 765
+T55J5 684
+KK677 28
+KTJJT 220
+QQQJA 483"#;
+
+    #[test]
+    fn day07_part1() {
+        assert_eq!(part1(PART1), 6440);
+    }
+
+    #[test]
+    fn day07_part2() {
+        assert_eq!(part2(PART1), 5905);
+    }
+
+    #[test]
+    fn day07_repl() {
+        let session = repl(PART1);
+        assert_eq!(
+            session.execute("hand", &["KTJJT"]).unwrap(),
+            Ok("part 1: two pair\npart 2: four of a kind".to_owned())
+        );
+        assert_eq!(
+            session.execute("rank", &["KTJJT"]).unwrap(),
+            Ok("part 1: two pair, rank 2 of 5, bet 220 wins 440\n\
+                part 2: four of a kind, rank 5 of 5, bet 220 wins 1100\n"
+                .to_owned())
+        );
+        assert!(session.execute("rank", &["22222"]).unwrap().is_err());
+        assert!(session.execute("hand", &["KTJJX"]).unwrap().is_err());
+    }
+
+    #[test]
+    fn day07_extra() {
+        assert_eq!(part2("JJJJJ 1337\n22223 326"), 3000);
+    }
+
+    #[test]
+    fn day07_explain() {
+        assert_eq!(
+            explain(PART1, 2).text(),
+            "hand   type            rank  bid  winnings\n\
+             32T3K  one pair           1  765       765\n\
+             KK677  two pair           2   28        56\n\
+             T55J5  four of a kind     3  684      2052\n\
+             QQQJA  four of a kind     4  483      1932\n\
+             KTJJT  four of a kind     5  220      1100\n"
+        );
+    }
+}
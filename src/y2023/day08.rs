@@ -0,0 +1,231 @@
+use crate::{debug, graph, hash::FastMap, math, repl};
+
+pub const TITLE: &str = "Haunted Wasteland";
+
+#[derive(Hash, PartialEq, Eq, Clone, Copy, Debug)]
+struct Id<'a>(&'a str);
+
+impl<'a> Id<'a> {
+    fn from_input(input: &'a str) -> Self {
+        assert_eq!(input.len(), 3);
+        for b in input.as_bytes() {
+            assert!(matches!(b, b'0'..=b'9' | b'A'..=b'Z'), "{input:?}");
+        }
+        Self(input)
+    }
+}
+
+#[derive(Default)]
+pub struct Map<'a> {
+    entries: FastMap<Id<'a>, (Id<'a>, Id<'a>)>,
+}
+
+impl<'a> Map<'a> {
+    fn add(&mut self, line: &'a str) {
+        let (entry, remain) = line.split_once(" = ").unwrap();
+        let (l, r) = remain
+            .strip_prefix('(')
+            .unwrap()
+            .strip_suffix(')')
+            .unwrap()
+            .split_once(", ")
+            .unwrap();
+        self.entries.insert(
+            Id::from_input(entry),
+            (Id::from_input(l), Id::from_input(r)),
+        );
+    }
+
+    /// One step of a ghost: the next node, and the next instruction to follow.
+    fn step(&self, instructions: &[u8], (node, inst): (Id<'a>, usize)) -> (Id<'a>, usize) {
+        let next = match instructions[inst] {
+            b'L' => self.entries[&node].0,
+            b'R' => self.entries[&node].1,
+            _ => unreachable!(),
+        };
+        (next, (inst + 1) % instructions.len())
+    }
+
+    fn count_steps(&self, instructions: &str, start: Id<'a>, is_end: impl Fn(Id) -> bool) -> usize {
+        let instructions = instructions.as_bytes();
+        let walk = std::iter::successors(Some((start, 0)), |&v| Some(self.step(instructions, v)));
+        walk.skip(1).position(|(node, _)| is_end(node)).unwrap() + 1
+    }
+}
+
+/// The instructions and the map.
+pub fn parse(input: &str) -> (&str, Map<'_>) {
+    let mut it = input.trim().lines();
+    let instructions = it.next().unwrap();
+    assert_eq!(it.next(), Some(""));
+    let mut map = Map::default();
+
+    for row in it {
+        map.add(row);
+    }
+
+    (instructions, map)
+}
+
+pub fn part1((instructions, map): &(&str, Map)) -> usize {
+    let end = Id::from_input("ZZZ");
+    map.count_steps(instructions, Id::from_input("AAA"), |v| v == end)
+}
+
+pub fn part2((instructions, map): &(&str, Map)) -> usize {
+    let mut counts = Vec::new();
+    for node in map.entries.keys().copied() {
+        if node.0.ends_with('A') {
+            let steps = map.count_steps(instructions, node, |v| v.0.ends_with('Z'));
+            let cycle = graph::find_cycle((node, 0), |&v| map.step(instructions.as_bytes(), v));
+            // The LCM only holds if the ghost is on an end at every multiple of `steps`. The
+            // states repeat from `cycle.start` on, so checking one cycle covers all of them.
+            assert!(
+                cycle.start <= steps && cycle.len % steps == 0,
+                "ghost {} reaches an end after {steps} steps, but cycles {cycle:?}",
+                node.0
+            );
+            let mut state = (node, 0);
+            for i in 1..cycle.start + cycle.len {
+                state = map.step(instructions.as_bytes(), state);
+                if i >= cycle.start && i % steps == 0 {
+                    assert!(
+                        state.0 .0.ends_with('Z'),
+                        "ghost {} reaches an end after {steps} steps, but is on {} after {i}",
+                        node.0,
+                        state.0 .0
+                    );
+                }
+            }
+            debug!(
+                "ghost {} reaches an end after {steps} steps, cycles {cycle:?}",
+                node.0
+            );
+            counts.push(steps);
+        }
+    }
+
+    math::checked_lcm_all(&counts).expect("step count overflows usize")
+}
+
+pub fn repl(data: &str) -> Box<dyn repl::Session + '_> {
+    Box::new(
+        repl::Commands::new(parse(data))
+            .command(
+                "path NODE [STEPS]",
+                "Walk from NODE until a node ending in Z, at most STEPS steps (default 100)",
+                |(instructions, map), args| {
+                    let mut cur = Id(args.first().ok_or("expected a node")?);
+                    if !map.entries.contains_key(&cur) {
+                        return Err(format!("no node {:?}", cur.0));
+                    }
+                    let steps: usize = match args.get(1) {
+                        Some(v) => v.parse().map_err(|_| "expected a number of steps")?,
+                        None => 100,
+                    };
+                    let mut ret = cur.0.to_owned();
+                    for (i, inst) in instructions.bytes().cycle().take(steps).enumerate() {
+                        let (l, r) = map.entries[&cur];
+                        cur = if inst == b'L' { l } else { r };
+                        ret += &format!(" -{}-> {}", char::from(inst), cur.0);
+                        if cur.0.ends_with('Z') {
+                            return Ok(format!("{ret}\nreached {} after {} steps", cur.0, i + 1));
+                        }
+                    }
+                    Ok(format!("{ret}\nno end within {steps} steps"))
+                },
+            )
+            .command(
+                "ghosts",
+                "Steps from every node ending in A to the first node ending in Z",
+                |(instructions, map), _| {
+                    let mut starts: Vec<_> =
+                        map.entries.keys().filter(|v| v.0.ends_with('A')).collect();
+                    starts.sort_by_key(|v| v.0);
+                    Ok(starts
+                        .into_iter()
+                        .map(|&node| {
+                            let steps = map.count_steps(instructions, node, |v| v.0.ends_with('Z'));
+                            format!("{}: {steps}\n", node.0)
+                        })
+                        .collect())
+                },
+            ),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DATA: &str = r#"
+RL
+
+AAA = (BBB, CCC)
+BBB = (DDD, EEE)
+CCC = (ZZZ, GGG)
+DDD = (DDD, DDD)
+EEE = (EEE, EEE)
+GGG = (GGG, GGG)
+ZZZ = (ZZZ, ZZZ)"#;
+
+    const DATA2: &str = r#"
+LLR
+
+AAA = (BBB, BBB)
+BBB = (AAA, ZZZ)
+ZZZ = (ZZZ, ZZZ)
+"#;
+
+    const DATA3: &str = r#"
+LR
+
+11A = (11B, XXX)
+11B = (XXX, 11Z)
+11Z = (11B, XXX)
+22A = (22B, XXX)
+22B = (22C, 22C)
+22C = (22Z, 22Z)
+22Z = (22B, 22B)
+XXX = (XXX, XXX)"#;
+
+    #[test]
+    fn day08_part1() {
+        assert_eq!(part1(&parse(DATA)), 2);
+        assert_eq!(part1(&parse(DATA2)), 6);
+    }
+
+    #[test]
+    fn day08_part2() {
+        assert_eq!(part2(&parse(DATA3)), 6);
+    }
+
+    #[test]
+    #[should_panic(expected = "ghost 33A reaches an end after 3 steps, but is on 33F after 6")]
+    fn day08_part2_uneven_ghost() {
+        // Cycles every 6 steps from 33B, but only reaches 33Z on the first 3rd step of each
+        let data = "L\n\n33A = (33B, XXX)\n33B = (33C, XXX)\n33C = (33Z, XXX)\n\
+                    33Z = (33D, XXX)\n33D = (33E, XXX)\n33E = (33F, XXX)\n33F = (33B, XXX)\n\
+                    XXX = (XXX, XXX)";
+        part2(&parse(data));
+    }
+
+    #[test]
+    fn day08_repl() {
+        let session = repl(DATA2);
+        assert_eq!(
+            session.execute("path", &["AAA"]).unwrap(),
+            Ok(
+                "AAA -L-> BBB -L-> AAA -R-> BBB -L-> AAA -L-> BBB -R-> ZZZ\n\
+                reached ZZZ after 6 steps"
+                    .to_owned()
+            )
+        );
+        assert!(session.execute("path", &["QQQ"]).unwrap().is_err());
+        let session = repl(DATA3);
+        assert_eq!(
+            session.execute("ghosts", &[]).unwrap(),
+            Ok("11A: 2\n22A: 3\n".to_owned())
+        );
+    }
+}
@@ -0,0 +1,84 @@
+use crate::{par, parse, scale::Rng};
+
+pub const TITLE: &str = "Mirage Maintenance";
+
+fn parse_line(line: &str) -> Vec<i64> {
+    parse::integers(line).unwrap()
+}
+
+fn extrapolate_last(cur: &[i64]) -> i64 {
+    if cur.iter().all(|&v| v == 0) {
+        return 0;
+    }
+
+    let next: Vec<_> = cur.windows(2).map(|v| v[1] - v[0]).collect();
+
+    let next_v = extrapolate_last(&next);
+    cur.last().unwrap() + next_v
+}
+
+fn extrapolate_first(cur: &[i64]) -> i64 {
+    if cur.iter().all(|&v| v == 0) {
+        return 0;
+    }
+
+    let next: Vec<_> = cur.windows(2).map(|v| v[1] - v[0]).collect();
+
+    let next_v = extrapolate_first(&next);
+    cur.first().unwrap() - next_v
+}
+
+pub fn validate(data: &str) {
+    data.trim().lines().for_each(|line| drop(parse_line(line)));
+}
+
+pub fn part1(data: &str) -> i64 {
+    par::sum_lines(data, |line| extrapolate_last(&parse_line(line)))
+}
+
+pub fn part2(data: &str) -> i64 {
+    par::sum_lines(data, |line| extrapolate_first(&parse_line(line)))
+}
+
+/// Sequences of values of random polynomials.
+pub fn generate(lines: usize, rng: &mut Rng) -> String {
+    let mut ret = String::new();
+    for _ in 0..lines {
+        let coefficients: Vec<i64> = (0..=rng.below(6))
+            .map(|_| rng.below(11) as i64 - 5)
+            .collect();
+        let values: Vec<String> = (0..21i64)
+            .map(|x| {
+                coefficients
+                    .iter()
+                    .rev()
+                    .fold(0, |acc, c| acc * x + c)
+                    .to_string()
+            })
+            .collect();
+        ret += &values.join(" ");
+        ret.push('\n');
+    }
+    ret
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DATA: &str = r#"
+0 3 6 9 12 15
+1 3 6 10 15 21
+10 13 16 21 30 45
+    "#;
+
+    #[test]
+    fn day09_part1() {
+        assert_eq!(part1(DATA), 114);
+    }
+
+    #[test]
+    fn day09_part2() {
+        assert_eq!(part2(DATA), 2);
+    }
+}
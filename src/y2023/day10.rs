@@ -1,4 +1,16 @@
-const PRINT_GRID: bool = false;
+use crate::{
+    graph,
+    grid::{self, Dir, Fill, Pos},
+    inspect::Format,
+    vis::{self, Color, Glyph},
+};
+
+pub const TITLE: &str = "Pipe Maze";
+
+pub const FORMAT: Format = Format {
+    chars: "|-LJ7F.S",
+    grid: true,
+};
 
 #[derive(Clone, Copy, PartialEq, Eq, Debug)]
 struct Pipe(u8);
@@ -40,70 +52,6 @@ impl Pipe {
     fn can_move_south(self) -> bool {
         (self.0 & Self::SOUTH.0) != 0
     }
-
-    fn move_trough(mut self, from: Pipe, pos: Pos) -> (Self, Pos) {
-        assert_eq!(from.0.count_ones(), 1);
-        assert_eq!(self.0 & from.0, from.0);
-        self.0 &= !from.0;
-        match self {
-            Pipe::NORTH => (Pipe::SOUTH, pos.move_north()),
-            Pipe::SOUTH => (Pipe::NORTH, pos.move_south()),
-            Pipe::EAST => (Pipe::WEST, pos.move_east()),
-            Pipe::WEST => (Pipe::EAST, pos.move_west()),
-            _ => unreachable!(),
-        }
-    }
-}
-
-#[derive(Clone, Copy, Debug, PartialEq, Eq)]
-struct Pos {
-    x: isize,
-    y: isize,
-}
-
-impl Pos {
-    fn new(x: usize, y: usize) -> Self {
-        Pos {
-            x: x.try_into().unwrap(),
-            y: y.try_into().unwrap(),
-        }
-    }
-
-    fn index(self, stride: usize) -> Option<usize> {
-        let x = usize::try_from(self.x).ok()?;
-        if x >= stride {
-            return None;
-        }
-        let y = usize::try_from(self.y).ok()?;
-        Some((y * stride) + x)
-    }
-
-    fn move_east(mut self) -> Pos {
-        self.x += 1;
-        self
-    }
-
-    fn move_north(mut self) -> Pos {
-        self.y -= 1;
-        self
-    }
-
-    fn move_south(mut self) -> Pos {
-        self.y += 1;
-        self
-    }
-
-    fn move_west(mut self) -> Pos {
-        self.x -= 1;
-        self
-    }
-
-    fn scale_up(&self, mul: isize) -> Self {
-        Self {
-            x: self.x * mul,
-            y: self.y * mul,
-        }
-    }
 }
 
 struct Walker {
@@ -111,7 +59,7 @@ struct Walker {
     total_steps: usize,
 }
 
-struct Grid {
+pub struct Grid {
     data: Vec<Pipe>,
     width: usize,
 }
@@ -136,45 +84,57 @@ impl Grid {
 
         let mut start_pipe = Pipe(0);
 
-        if self.get(start.move_east()).can_move_west() {
+        if self.get(start + Dir::R).can_move_west() {
             start_pipe.0 |= Pipe::EAST.0;
         }
 
-        if self.get(start.move_south()).can_move_north() {
+        if self.get(start + Dir::D).can_move_north() {
             start_pipe.0 |= Pipe::SOUTH.0;
         }
 
-        if start.x > 0 && self.get(start.move_west()).can_move_east() {
+        if start.x > 0 && self.get(start + Dir::L).can_move_east() {
             start_pipe.0 |= Pipe::WEST.0;
         }
 
-        if start.y > 0 && self.get(start.move_north()).can_move_south() {
+        if start.y > 0 && self.get(start + Dir::U).can_move_south() {
             start_pipe.0 |= Pipe::NORTH.0;
         }
 
-        let mut from = Pipe(0x80 >> start_pipe.0.leading_zeros());
-        let mut pos = start;
+        let pipe_at = |pos: Pos| {
+            if pos == start {
+                start_pipe
+            } else {
+                self.get(pos)
+            }
+        };
+        let dist = graph::bfs(start, |&pos| {
+            let pipe = pipe_at(pos);
+            [
+                (pipe.can_move_north(), pos + Dir::U),
+                (pipe.can_move_south(), pos + Dir::D),
+                (pipe.can_move_east(), pos + Dir::R),
+                (pipe.can_move_west(), pos + Dir::L),
+            ]
+            .into_iter()
+            .filter_map(|(open, next)| open.then_some(next))
+        });
+
+        // Both ends of the loop grow at once, from the start to the farthest point
+        let mut walked: Vec<_> = dist.into_iter().collect();
+        walked.sort_by_key(|&(pos, d)| (d, pos.y, pos.x));
 
         let mut image = FilledGrid::new(self.width, self.data.len() / self.width);
-        let mut total_steps = 1;
-
-        (from, pos) = start_pipe.move_trough(from, pos);
-        image.set(start, b'S', start_pipe);
-
-        loop {
-            total_steps += 1;
-
-            let pipe = self.get(pos);
-
-            image.set(pos, b'*', pipe);
-
-            (from, pos) = pipe.move_trough(from, pos);
-
-            if pos == start {
-                break;
+        for (i, &(pos, _)) in walked.iter().enumerate() {
+            let value = if pos == start { b'S' } else { b'*' };
+            image.set(pos, value, pipe_at(pos));
+            if (i + 1) % self.width == 0 {
+                image.show(&format!("Walking the loop, step {}", i + 1));
             }
         }
 
+        let total_steps = walked.len();
+        image.show(&format!("Loop closed after {total_steps} steps"));
+
         Walker { image, total_steps }
     }
 
@@ -187,7 +147,6 @@ impl Grid {
 
 struct FilledGrid {
     data: Vec<u8>,
-    width: usize,
     stride: usize,
 }
 
@@ -197,13 +156,16 @@ impl FilledGrid {
         let raw_height = 2 + (height * 3);
         Self {
             data: vec![b' '; raw_height * raw_width],
-            width,
             stride: raw_width,
         }
     }
 
     fn set(&mut self, pos: Pos, value: u8, pipe: Pipe) {
-        let mut base = pos.scale_up(3).index(self.stride).unwrap() + 1 + self.stride;
+        let scaled = Pos {
+            x: pos.x * 3,
+            y: pos.y * 3,
+        };
+        let mut base = scaled.index(self.stride).unwrap() + 1 + self.stride;
 
         //
         // Row 1
@@ -246,7 +208,35 @@ impl FilledGrid {
         }
     }
 
-    fn try_set_raw(&mut self, pos: Pos) -> bool {
+    fn glyph(&self, x: usize, y: usize) -> Glyph {
+        match self.data[(y * self.stride) + x] {
+            b'S' => Glyph::new('S', Color::Green),
+            b'*' => Glyph::new('*', Color::Yellow),
+            b'-' => Glyph::new('-', Color::Gray),
+            b @ (b'#' | b'@') => Glyph::new(char::from(b), Color::Blue),
+            b => char::from(b).into(),
+        }
+    }
+
+    fn show(&self, title: &str) {
+        if !vis::enabled() {
+            return;
+        }
+        vis::frame(title, self.stride, self.data.len() / self.stride, |x, y| {
+            self.glyph(x, y)
+        });
+    }
+}
+
+impl Fill for FilledGrid {
+    /// Both untouched space and the gaps around pipes the loop doesn't connect are open.
+    fn can_fill(&self, pos: Pos) -> bool {
+        pos.index(self.stride)
+            .and_then(|i| self.data.get(i))
+            .is_some_and(|v| matches!(v, b' ' | b'-'))
+    }
+
+    fn try_fill(&mut self, pos: Pos) -> bool {
         match pos.index(self.stride).and_then(|i| self.data.get_mut(i)) {
             Some(v @ b' ') => {
                 *v = b'#';
@@ -259,113 +249,28 @@ impl FilledGrid {
             _ => false,
         }
     }
-
-    fn is_set_raw(&self, pos: Pos) -> bool {
-        if let Some(v) = pos.index(self.stride).and_then(|i| self.data.get(i)) {
-            matches!(v, b' ' | b'-')
-        } else {
-            false
-        }
-    }
-
-    fn flood_fill(&mut self, pos: Pos) {
-        let mut pending = vec![(pos.x, pos.x, pos.y, 1)];
-
-        if pos.y > 0 {
-            pending.push((pos.x, pos.x, pos.y - 1, -1));
-        }
-
-        while let Some((mut x1, x2, y, dy)) = pending.pop() {
-            let mut x = x1;
-            if self.is_set_raw(Pos { x, y }) {
-                while self.try_set_raw(Pos { x: x - 1, y }) {
-                    x -= 1;
-                }
-                if x < x1 {
-                    pending.push((x, x1 - 1, y - dy, -dy));
-                }
-            }
-
-            while x1 <= x2 {
-                while self.try_set_raw(Pos { x: x1, y }) {
-                    x1 += 1;
-                }
-
-                if x1 > x {
-                    pending.push((x, x1 - 1, y + dy, dy));
-                }
-
-                if x1 - 1 > x2 {
-                    pending.push((x2 + 1, x1 - 1, y - dy, -dy));
-                }
-
-                x1 += 1;
-
-                while x1 < x2 && !self.is_set_raw(Pos { x: x1, y }) {
-                    x1 += 1;
-                }
-
-                x = x1;
-            }
-        }
-    }
-
-    fn print_grid(&self) {
-        if !PRINT_GRID {
-            return;
-        }
-        println!();
-        if self.width < 10 {
-            print!("     ");
-            for i in 0..self.width {
-                print!("{0}{0}{0}", i % 10);
-            }
-            println!();
-        }
-        for (i, line) in self.data[1..].chunks(self.stride).skip(1).enumerate() {
-            println!("{:>3} |{}|", i / 3, std::str::from_utf8(&line).unwrap());
-        }
-        println!();
-    }
 }
 
-fn part1(data: &str) -> usize {
-    if PRINT_GRID {
-        println!("\n{}\n", data.trim());
-    }
+pub fn parse(data: &str) -> Grid {
+    Grid::from_input(data)
+}
 
-    let grid = Grid::from_input(data);
+pub fn part1(grid: &Grid) -> usize {
     let res = grid.walk_grid();
 
-    res.image.print_grid();
-
     (res.total_steps / 2) + (res.total_steps % 2)
 }
 
-fn part2(data: &str) -> usize {
-    if PRINT_GRID {
-        println!("\n{}\n", data.trim());
-    }
-
-    let grid = Grid::from_input(data);
+pub fn part2(grid: &Grid) -> usize {
     let mut res = grid.walk_grid();
 
-    res.image.print_grid();
-
-    res.image.flood_fill(Pos { x: 0, y: 0 });
+    grid::flood_fill(&mut res.image, Pos { x: 0, y: 0 });
 
-    res.image.print_grid();
+    res.image.show("Flood filled from the outside");
 
     res.image.data.iter().filter(|&&b| b == b' ').count() / 9
 }
 
-fn main() {
-    println!("Day 10");
-    let data = std::fs::read_to_string("input/day10.txt").unwrap();
-    println!("Part 1: {}", part1(&data));
-    println!("Part 2: {}", part2(&data));
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -420,26 +325,40 @@ L--J.L7...LJS7F-7L7.
 
     #[test]
     fn day10_part1_data1() {
-        assert_eq!(part1(DATA1), 4);
+        assert_eq!(part1(&parse(DATA1)), 4);
     }
 
     #[test]
     fn day10_part1_data2() {
-        assert_eq!(part1(DATA2), 8);
+        assert_eq!(part1(&parse(DATA2)), 8);
     }
 
     #[test]
     fn day10_part2_data3() {
-        assert_eq!(part2(DATA3), 4);
+        assert_eq!(part2(&parse(DATA3)), 4);
     }
 
     #[test]
     fn day10_part2_data4() {
-        assert_eq!(part2(DATA4), 4);
+        assert_eq!(part2(&parse(DATA4)), 4);
     }
 
     #[test]
     fn day10_part2_data5() {
-        assert_eq!(part2(DATA5), 8);
+        assert_eq!(part2(&parse(DATA5)), 8);
+    }
+
+    #[test]
+    fn day10_flood_fill_snapshot() {
+        let mut res = parse(DATA4).walk_grid();
+        grid::flood_fill(&mut res.image, Pos { x: 0, y: 0 });
+        let image = &res.image;
+        let rendered = vis::render(
+            image.stride,
+            image.data.len() / image.stride,
+            false,
+            |x, y| image.glyph(x, y),
+        );
+        crate::snapshot::assert_snapshot("day10_flood_fill", &rendered);
     }
 }
@@ -1,3 +1,12 @@
+use crate::{arith, inspect::Format, scale::Rng};
+
+pub const TITLE: &str = "Cosmic Expansion";
+
+pub const FORMAT: Format = Format {
+    chars: ".#",
+    grid: true,
+};
+
 enum Tile {
     Galaxy,
     Empty { h: usize, v: usize },
@@ -51,7 +60,7 @@ impl Universe {
         }
     }
 
-    fn iter_galaxies(&self) -> GalaxyIter {
+    fn iter_galaxies(&self) -> GalaxyIter<'_> {
         let mut rows = self.grid.chunks_exact(self.width);
         GalaxyIter {
             cur_x: 0,
@@ -84,11 +93,11 @@ impl<'a> Iterator for GalaxyIter<'a> {
                 }
                 Some(&Tile::Empty { h, v }) => {
                     self.y_add = self.y_add.max(h);
-                    self.cur_x += v;
+                    self.cur_x = arith::add(self.cur_x, v);
                 }
                 None => {
                     self.tiles = self.rows.next()?.iter();
-                    self.cur_y += self.y_add;
+                    self.cur_y = arith::add(self.cur_y, self.y_add);
                     self.cur_x = 0;
                     self.y_add = 1;
                 }
@@ -104,7 +113,7 @@ struct Pos {
 
 impl Pos {
     fn ortho_distance(&self, other: &Self) -> usize {
-        self.x.abs_diff(other.x) + self.y.abs_diff(other.y)
+        arith::add(self.x.abs_diff(other.x), self.y.abs_diff(other.y))
     }
 }
 
@@ -115,31 +124,33 @@ fn expand_and_sum_distances(data: &str, count: usize) -> usize {
 
     let galaxies: Vec<_> = universe.iter_galaxies().collect();
 
-    galaxies
-        .iter()
-        .enumerate()
-        .flat_map(|(i, a)| {
-            galaxies
-                .iter()
-                .skip(i + 1)
-                .map(move |b| a.ortho_distance(&b))
-        })
-        .sum()
+    arith::sum(galaxies.iter().enumerate().flat_map(|(i, a)| {
+        galaxies
+            .iter()
+            .skip(i + 1)
+            .map(move |b| a.ortho_distance(b))
+    }))
 }
 
-fn part1(data: &str) -> usize {
+pub fn validate(data: &str) {
+    Universe::from_input(data);
+}
+
+pub fn part1(data: &str) -> usize {
     expand_and_sum_distances(data, 2)
 }
 
-fn part2(data: &str) -> usize {
+pub fn part2(data: &str) -> usize {
     expand_and_sum_distances(data, 1_000_000)
 }
 
-fn main() {
-    println!("Day 11");
-    let data = std::fs::read_to_string("input/day11.txt").unwrap();
-    println!("Part 1: {}", part1(&data));
-    println!("Part 2: {}", part2(&data));
+pub fn generate(side: usize, rng: &mut Rng) -> String {
+    let mut ret = String::new();
+    for _ in 0..side {
+        ret.extend((0..side).map(|_| if rng.chance(4) { '#' } else { '.' }));
+        ret.push('\n');
+    }
+    ret
 }
 
 #[cfg(test)]
@@ -1,9 +1,14 @@
-use std::collections::HashMap;
+use crate::{debug, memo::Memo, par};
+
+pub const TITLE: &str = "Hot Springs";
+
+/// Arrangements are cached by the number of springs and counts left.
+type Cache = Memo<(usize, usize), usize>;
 
 struct State<'a, 'cache> {
     springs: &'a [u8],
     counts: &'a [usize],
-    cache: &'cache mut HashMap<(usize, usize), usize>,
+    cache: &'cache mut Cache,
 }
 
 impl<'a, 'cache> State<'a, 'cache> {
@@ -46,23 +51,16 @@ impl<'a, 'cache> State<'a, 'cache> {
         if count <= sequence.len() {
             for i in count..=sequence.len() {
                 let (cur, remainder) = springs.split_at(i);
-                let res = if let Some(&res) =
-                    self.cache.get(&(remainder.len(), remaining_counts.len()))
-                {
-                    res
-                } else {
-                    let res = State {
-                        springs: remainder,
-                        counts: remaining_counts,
-                        cache: self.cache,
-                    }
-                    .count_arrangements(1);
-                    self.cache
-                        .insert((remainder.len(), remaining_counts.len()), res);
-                    res
-                };
-
-                ret += res;
+                ret += self
+                    .cache
+                    .get((remainder.len(), remaining_counts.len()), |cache| {
+                        State {
+                            springs: remainder,
+                            counts: remaining_counts,
+                            cache,
+                        }
+                        .count_arrangements(1)
+                    });
 
                 // If the current sequence starts with a damaged spring, we only need to check the first
                 if cur[i - count] == b'#' {
@@ -84,53 +82,56 @@ impl<'a, 'cache> State<'a, 'cache> {
     }
 }
 
-fn part1(data: &str) -> usize {
-    data.trim()
-        .lines()
-        .map(|line| {
-            let (springs, counts) = line.split_once(' ').unwrap();
-            let counts: Vec<_> = counts.split(',').map(|v| v.parse().unwrap()).collect();
-            State {
-                springs: springs.as_bytes(),
-                counts: &counts,
-                cache: &mut HashMap::new(),
-            }
-            .count_arrangements(0)
-        })
-        .sum()
+/// The springs and the counts of damaged ones in a line like `???.### 1,1,3`.
+fn parse_line(line: &str) -> (&[u8], Vec<usize>) {
+    let (springs, counts) = line.split_once(' ').unwrap();
+    assert!(
+        springs.bytes().all(|b| matches!(b, b'.' | b'#' | b'?')),
+        "{line:?}"
+    );
+    let counts = counts.split(',').map(|v| v.parse().unwrap()).collect();
+    (springs.as_bytes(), counts)
 }
 
-fn part2(data: &str) -> usize {
-    data.trim()
-        .lines()
-        .map(|line| {
-            let (raw_springs, counts) = line.split_once(' ').unwrap();
-            let mut springs = Vec::with_capacity((raw_springs.len() + 1) * 5);
-            for _ in 0..5 {
-                springs.extend_from_slice(raw_springs.as_bytes());
-                springs.push(b'?');
-            }
-            springs.pop();
-            let counts: Vec<usize> = counts
-                .split(',')
-                .map(|v| v.parse().unwrap())
-                .collect::<Vec<_>>()
-                .repeat(5);
-            State {
-                springs: &springs,
-                counts: &counts,
-                cache: &mut HashMap::new(),
-            }
-            .count_arrangements(0)
-        })
-        .sum()
+pub fn validate(data: &str) {
+    data.trim().lines().for_each(|line| drop(parse_line(line)));
 }
 
-fn main() {
-    println!("Day 12");
-    let data = std::fs::read_to_string("input/day12.txt").unwrap();
-    println!("Part 1: {}", part1(&data));
-    println!("Part 2: {}", part2(&data));
+pub fn part1(data: &str) -> usize {
+    par::sum_lines(data, |line| {
+        let (springs, counts) = parse_line(line);
+        let mut cache = Cache::new();
+        let res = State {
+            springs,
+            counts: &counts,
+            cache: &mut cache,
+        }
+        .count_arrangements(0);
+        debug!("{line}: {res} arrangements, cache {}", cache.stats());
+        res
+    })
+}
+
+pub fn part2(data: &str) -> usize {
+    par::sum_lines(data, |line| {
+        let (raw_springs, counts) = parse_line(line);
+        let mut springs = Vec::with_capacity((raw_springs.len() + 1) * 5);
+        for _ in 0..5 {
+            springs.extend_from_slice(raw_springs);
+            springs.push(b'?');
+        }
+        springs.pop();
+        let counts = counts.repeat(5);
+        let mut cache = Cache::new();
+        let res = State {
+            springs: &springs,
+            counts: &counts,
+            cache: &mut cache,
+        }
+        .count_arrangements(0);
+        debug!("{line}: {res} arrangements, cache {}", cache.stats());
+        res
+    })
 }
 
 #[cfg(test)]
@@ -154,4 +155,11 @@ mod tests {
     fn day12_part2() {
         assert_eq!(part2(DATA1), 525152);
     }
+
+    #[test]
+    fn day12_validate() {
+        validate(DATA1);
+        let err = crate::inspect::validate(validate, "???.### 1,1,3\n??x.# 1,1");
+        assert!(err.is_err(), "{err:?}");
+    }
 }
@@ -1,4 +1,13 @@
-struct Map<'a> {
+use crate::inspect::Format;
+
+pub const TITLE: &str = "Point of Incidence";
+
+pub const FORMAT: Format = Format {
+    chars: ".#",
+    grid: true,
+};
+
+pub struct Map<'a> {
     data: &'a [u8],
     width: usize,
     height: usize,
@@ -45,10 +54,12 @@ impl<'a> Map<'a> {
     }
 }
 
-fn part1(data: &str) -> usize {
-    data.trim()
-        .split("\n\n")
-        .map(Map::from_input)
+pub fn parse(data: &str) -> Vec<Map<'_>> {
+    data.trim().split("\n\n").map(Map::from_input).collect()
+}
+
+pub fn part1(maps: &[Map]) -> usize {
+    maps.iter()
         .map(|map| {
             if let Some(y) = (1..=map.height).find(|&y| map.mirror_iter_h(y).all(|(a, b)| a.eq(b)))
             {
@@ -64,7 +75,7 @@ fn part1(data: &str) -> usize {
         .sum()
 }
 
-fn part2(data: &str) -> usize {
+pub fn part2(maps: &[Map]) -> usize {
     #[derive(Default)]
     struct CmpState {
         num_mismatch: usize,
@@ -89,9 +100,7 @@ fn part2(data: &str) -> usize {
         }
     }
 
-    data.trim()
-        .split("\n\n")
-        .map(Map::from_input)
+    maps.iter()
         .map(|map| {
             if let Some(y) = (1..=map.height).find(|&y| {
                 let mut state = CmpState::default();
@@ -112,13 +121,6 @@ fn part2(data: &str) -> usize {
         .sum()
 }
 
-fn main() {
-    println!("Day 13");
-    let data = std::fs::read_to_string("input/day13.txt").unwrap();
-    println!("Part 1: {}", part1(&data));
-    println!("Part 2: {}", part2(&data));
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -142,11 +144,11 @@ mod tests {
 
     #[test]
     fn day13_part1() {
-        assert_eq!(part1(DATA1), 405);
+        assert_eq!(part1(&parse(DATA1)), 405);
     }
 
     #[test]
     fn day13_part2() {
-        assert_eq!(part2(DATA1), 400);
+        assert_eq!(part2(&parse(DATA1)), 400);
     }
 }
@@ -1,6 +1,15 @@
-use std::{
-    collections::HashMap,
-    hash::{Hash, Hasher},
+use crate::{
+    debug,
+    hash::{self, FastMap},
+    inspect::Format,
+    vis::{self, Color, Glyph},
+};
+
+pub const TITLE: &str = "Parabolic Reflector Dish";
+
+pub const FORMAT: Format = Format {
+    chars: ".#O",
+    grid: true,
 };
 
 fn roll_row_of_tiles<'a, 'b>(
@@ -22,14 +31,15 @@ fn roll_row_of_tiles<'a, 'b>(
     changes_made
 }
 
-#[derive(Debug, Hash)]
+#[derive(Clone, Debug, Hash)]
 enum Tile {
     Empty,
     Roll,
     Fixed,
 }
 
-struct Map {
+#[derive(Clone)]
+pub struct Map {
     data: Vec<Tile>,
     width: usize,
     height: usize,
@@ -42,8 +52,7 @@ impl Map {
         let data: Vec<_> = data
             .trim()
             .lines()
-            .map(|l| l.as_bytes())
-            .flatten()
+            .flat_map(|l| l.as_bytes())
             .copied()
             .map(|v| match v {
                 b'.' => Tile::Empty,
@@ -152,32 +161,53 @@ impl Map {
             .sum()
     }
 
+    fn glyph(&self, x: usize, y: usize) -> Glyph {
+        match self.data[(y * self.width) + x] {
+            Tile::Empty => '.'.into(),
+            Tile::Roll => Glyph::new('O', Color::Yellow),
+            Tile::Fixed => Glyph::new('#', Color::Gray),
+        }
+    }
+
+    fn show(&self, title: &str) {
+        vis::frame(title, self.width, self.height, |x, y| self.glyph(x, y));
+    }
+
     fn cur_hash(&self) -> u64 {
-        let mut h = std::collections::hash_map::DefaultHasher::new();
-        Tile::hash_slice(&self.data, &mut h);
-        h.finish()
+        hash::hash_one(&self.data)
     }
 }
 
-fn part1(data: &str) -> usize {
-    let mut map = Map::from_input(data);
+pub fn parse(data: &str) -> Map {
+    Map::from_input(data)
+}
+
+pub fn part1(map: &Map) -> usize {
+    let mut map = map.clone();
     map.tilt_north();
+    map.show("Tilted north");
     map.calc_north_load()
 }
 
-fn part2(data: &str) -> usize {
+pub fn part2(map: &Map) -> usize {
     const ITERATIONS: usize = 1_000_000_000;
-    let mut map = Map::from_input(data);
-    let mut seen_hashes = HashMap::new();
+    let mut map = map.clone();
+    let mut seen_hashes = FastMap::default();
     for i in 0..ITERATIONS {
         map.tilt_north();
         map.tilt_west();
         map.tilt_south();
         map.tilt_east();
+        if vis::enabled() {
+            map.show(&format!("After cycle {}", i + 1));
+        }
         if let Some(prev_i) = seen_hashes.insert(map.cur_hash(), i) {
             let iterations_per = i - prev_i;
             let remaining = ITERATIONS - i;
             if (remaining % iterations_per) == 1 {
+                debug!(
+                    "cycle of {iterations_per} iterations, first seen at {prev_i}, stopping at {i}"
+                );
                 break;
             }
         }
@@ -185,13 +215,6 @@ fn part2(data: &str) -> usize {
     map.calc_north_load()
 }
 
-fn main() {
-    println!("Day 14");
-    let data = std::fs::read_to_string("input/day14.txt").unwrap();
-    println!("Part 1: {}", part1(&data));
-    println!("Part 2: {}", part2(&data));
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -210,11 +233,26 @@ O.#..O.#.#
 
     #[test]
     fn day14_part1() {
-        assert_eq!(part1(DATA1), 136);
+        assert_eq!(part1(&parse(DATA1)), 136);
     }
 
     #[test]
     fn day14_part2() {
-        assert_eq!(part2(DATA1), 64);
+        assert_eq!(part2(&parse(DATA1)), 64);
+    }
+
+    #[test]
+    fn day14_cycle_snapshot() {
+        let mut map = parse(DATA1);
+        let mut rendered = String::new();
+        for i in 1..=3 {
+            map.tilt_north();
+            map.tilt_west();
+            map.tilt_south();
+            map.tilt_east();
+            rendered += &format!("After {i} cycles:\n");
+            rendered += &vis::render(map.width, map.height, false, |x, y| map.glyph(x, y));
+        }
+        crate::snapshot::assert_snapshot("day14_cycles", &rendered);
     }
 }
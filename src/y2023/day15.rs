@@ -1,4 +1,6 @@
-use std::collections::HashMap;
+use crate::hash::FastMap;
+
+pub const TITLE: &str = "Lens Library";
 
 fn hash_str(v: &str) -> usize {
     v.as_bytes()
@@ -9,28 +11,50 @@ fn hash_str(v: &str) -> usize {
         })
         .into()
 }
-fn part1(data: &str) -> usize {
+
+/// A step like `rn=1`, putting a lens in, or `cm-`, taking it out.
+enum Step<'a> {
+    Remove(&'a str),
+    Insert(&'a str, usize),
+}
+
+impl<'a> Step<'a> {
+    fn from_input(input: &'a str) -> Self {
+        match input.split_once(['-', '=']) {
+            Some((lens, "")) => Self::Remove(lens),
+            Some((lens, focal)) => Self::Insert(lens, focal.parse().unwrap()),
+            None => panic!("unexpected step {input:?}"),
+        }
+    }
+}
+
+pub fn validate(data: &str) {
+    for step in data.trim().split(',') {
+        Step::from_input(step);
+    }
+}
+
+pub fn part1(data: &str) -> usize {
     data.trim().split(',').map(hash_str).sum()
 }
 
-fn part2(data: &str) -> usize {
-    let mut maps = [(); 256].map(|()| HashMap::new());
+pub fn part2(data: &str) -> usize {
+    let mut maps = [(); 256].map(|()| FastMap::default());
     let mut index = 0;
-    for cur in data.trim().split(',') {
-        match cur.split_once(['-', '=']) {
-            Some((lens, "")) => {
+    for step in data.trim().split(',').map(Step::from_input) {
+        match step {
+            Step::Remove(lens) => {
                 maps[hash_str(lens)].remove(lens);
             }
-            Some((lens, focal)) => {
+            Step::Insert(lens, focal) => {
                 maps[hash_str(lens)]
                     .entry(lens)
                     .or_insert_with(|| {
                         index += 1;
                         (index, 0)
                     })
-                    .1 = focal.parse().unwrap()
+                    .1 = focal
             }
-            None => unreachable!(),
         }
     }
     maps.iter()
@@ -48,13 +72,6 @@ fn part2(data: &str) -> usize {
         .sum()
 }
 
-fn main() {
-    println!("Day 15");
-    let data = std::fs::read_to_string("input/day15.txt").unwrap();
-    println!("Part 1: {}", part1(&data));
-    println!("Part 2: {}", part2(&data));
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -79,4 +96,11 @@ rn=1,cm-,qp=3,cm=2,qp-,pc=4,ot=9,ab=5,pc-,pc=6,ot=7
     fn day15_part2() {
         assert_eq!(part2(DATA1), 145);
     }
+
+    #[test]
+    fn day15_validate() {
+        validate(DATA1);
+        let err = crate::inspect::validate(validate, "rn=1,cm");
+        assert!(err.is_err(), "{err:?}");
+    }
 }
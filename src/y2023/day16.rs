@@ -1,10 +1,25 @@
+use crate::{
+    grid::Pos,
+    inspect::Format,
+    scale::Rng,
+    vis::{self, Color, Glyph},
+};
+
+pub const TITLE: &str = "The Floor Will Be Lava";
+
+pub const FORMAT: Format = Format {
+    chars: ".|-/\\",
+    grid: true,
+};
+
 const MIRROR_F: u8 = 0b0100;
 const MIRROR_B: u8 = 0b0101;
 const SPLIT_V: u8 = 0b0110;
 const SPLIT_H: u8 = 0b0111;
 const TYPE_MASK: u8 = 0b0111;
 
-struct Map {
+#[derive(Clone)]
+pub struct Map {
     data: Vec<u8>,
     width: usize,
 }
@@ -74,30 +89,39 @@ impl Map {
     fn reset(&mut self) {
         self.data.iter_mut().for_each(|v| *v &= 0b1111);
     }
-}
-
-#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
-struct Pos {
-    x: isize,
-    y: isize,
-}
 
-impl Pos {
-    fn index(self, width: usize) -> Option<usize> {
-        let x = usize::try_from(self.x).ok()?;
-        if x < width {
-            let y = usize::try_from(self.y).ok()?;
-            Some((y * width) + x)
+    fn glyph(&self, x: usize, y: usize) -> Glyph {
+        let cur = self.data[(y * self.width) + x];
+        let ch = match cur & TYPE_MASK {
+            MIRROR_F => '/',
+            MIRROR_B => '\\',
+            SPLIT_V => '|',
+            SPLIT_H => '-',
+            _ if cur > 0b1111 => '#',
+            _ => '.',
+        };
+        if cur > 0b1111 {
+            Glyph::new(ch, Color::Yellow)
         } else {
-            None
+            ch.into()
         }
     }
 
-    fn new(x: usize, y: usize) -> Pos {
-        Self {
-            x: x.try_into().unwrap(),
-            y: y.try_into().unwrap(),
+    fn show(&self, title: &str) {
+        let height = self.data.len() / self.width;
+        vis::frame(title, self.width, height, |x, y| self.glyph(x, y));
+    }
+
+    fn energize_from(&mut self, pos: Pos, dir: Dir) -> usize {
+        let ret = self.trace_beam(pos, dir);
+        if vis::enabled() {
+            self.show(&format!(
+                "Entering at ({}, {}) going {dir:?}: {ret} energized",
+                pos.x, pos.y
+            ));
         }
+        self.reset();
+        ret
     }
 }
 
@@ -157,38 +181,46 @@ impl std::ops::Add<Dir> for Pos {
     }
 }
 
-fn part1(data: &str) -> usize {
-    let mut map = Map::from_input(data);
-    map.trace_beam(Pos { x: 0, y: 0 }, Dir::R)
+pub fn parse(data: &str) -> Map {
+    Map::from_input(data)
+}
+
+pub fn part1(map: &Map) -> usize {
+    let mut map = map.clone();
+    map.energize_from(Pos { x: 0, y: 0 }, Dir::R)
 }
 
-fn part2(data: &str) -> usize {
-    let mut map = Map::from_input(data);
+pub fn part2(map: &Map) -> usize {
+    let mut map = map.clone();
     let height = map.data.len() / map.width;
     let mut ret = 0;
 
     for x in 0..map.width {
-        ret = ret.max(map.trace_beam(Pos::new(x, 0), Dir::D));
-        map.reset();
-        ret = ret.max(map.trace_beam(Pos::new(x, height - 1), Dir::U));
-        map.reset();
+        ret = ret.max(map.energize_from(Pos::new(x, 0), Dir::D));
+        ret = ret.max(map.energize_from(Pos::new(x, height - 1), Dir::U));
     }
 
     for y in 0..height {
-        ret = ret.max(map.trace_beam(Pos::new(0, y), Dir::R));
-        map.reset();
-        ret = ret.max(map.trace_beam(Pos::new(map.width - 1, y), Dir::L));
-        map.reset();
+        ret = ret.max(map.energize_from(Pos::new(0, y), Dir::R));
+        ret = ret.max(map.energize_from(Pos::new(map.width - 1, y), Dir::L));
     }
 
     ret
 }
 
-fn main() {
-    println!("Day 16");
-    let data = std::fs::read_to_string("input/day16.txt").unwrap();
-    println!("Part 1: {}", part1(&data));
-    println!("Part 2: {}", part2(&data));
+pub fn generate(side: usize, rng: &mut Rng) -> String {
+    let mut ret = String::new();
+    for _ in 0..side {
+        ret.extend((0..side).map(|_| {
+            if rng.chance(85) {
+                '.'
+            } else {
+                *rng.pick(&['|', '-', '/', '\\'])
+            }
+        }));
+        ret.push('\n');
+    }
+    ret
 }
 
 #[cfg(test)]
@@ -209,11 +241,20 @@ mod tests {
 
     #[test]
     fn day16_part1() {
-        assert_eq!(part1(DATA1), 46);
+        assert_eq!(part1(&parse(DATA1)), 46);
     }
 
     #[test]
     fn day16_part2() {
-        assert_eq!(part2(DATA1), 51);
+        assert_eq!(part2(&parse(DATA1)), 51);
+    }
+
+    #[test]
+    fn day16_energized_snapshot() {
+        let mut map = parse(DATA1);
+        map.trace_beam(Pos { x: 0, y: 0 }, Dir::R);
+        let height = map.data.len() / map.width;
+        let rendered = vis::render(map.width, height, false, |x, y| map.glyph(x, y));
+        crate::snapshot::assert_snapshot("day16_energized", &rendered);
     }
 }
@@ -0,0 +1,184 @@
+use crate::{
+    debug, graph,
+    grid::{Dir, Pos},
+    inspect::Format,
+    scale::Rng,
+    vis::{self, Color, Glyph},
+};
+
+pub const TITLE: &str = "Clumsy Crucible";
+
+pub const FORMAT: Format = Format {
+    chars: "123456789",
+    grid: true,
+};
+
+pub struct Map {
+    data: Vec<u8>,
+    width: usize,
+    height: usize,
+}
+
+impl Map {
+    fn from_input(data: &str) -> Self {
+        let data = data.trim();
+        let width = data.lines().next().unwrap().len();
+        let data: Vec<u8> = data
+            .lines()
+            .flat_map(|v| v.as_bytes())
+            .map(|b| match b {
+                b'1'..=b'9' => b - b'0',
+                _ => panic!("unexpected {:?}", *b as char),
+            })
+            .collect();
+        assert_eq!(data.len() % width, 0, "uneven rows");
+
+        Self {
+            height: data.len() / width,
+            data,
+            width,
+        }
+    }
+
+    fn heat_loss(&self, pos: Pos) -> Option<u64> {
+        let i = pos.index(self.width)?;
+        self.data.get(i).map(|&v| u64::from(v))
+    }
+
+    /// The least heat lost getting from the top left to the bottom right, moving between
+    /// `min_run` and `max_run` blocks in a straight line before every turn.
+    fn min_heat_loss(&self, min_run: usize, max_run: usize) -> u64 {
+        let start = State {
+            pos: Pos { x: 0, y: 0 },
+            dir: None,
+        };
+        let end = Pos::new(self.width - 1, self.height - 1);
+
+        let (ret, path) = graph::dijkstra(
+            start,
+            |state| {
+                let turns = match state.dir {
+                    None => [Dir::R, Dir::D],
+                    Some(Dir::L | Dir::R) => [Dir::U, Dir::D],
+                    Some(Dir::U | Dir::D) => [Dir::L, Dir::R],
+                };
+                let mut ret = Vec::new();
+                for dir in turns {
+                    let mut pos = state.pos;
+                    let mut cost = 0;
+                    for run in 1..=max_run {
+                        pos = pos + dir;
+                        let Some(loss) = self.heat_loss(pos) else {
+                            break;
+                        };
+                        cost += loss;
+                        if run >= min_run {
+                            ret.push((
+                                State {
+                                    pos,
+                                    dir: Some(dir),
+                                },
+                                cost,
+                            ));
+                        }
+                    }
+                }
+                ret
+            },
+            |state| state.pos == end,
+        )
+        .expect("no path to the bottom right");
+
+        debug!(
+            "runs {min_run}..={max_run}: {ret} heat loss in {} straight runs",
+            path.len() - 1
+        );
+        if vis::enabled() {
+            let path: Vec<Pos> = path.iter().map(|v| v.pos).collect();
+            vis::frame(
+                &format!("Runs {min_run}..={max_run}: {ret} heat loss"),
+                self.width,
+                self.height,
+                |x, y| self.glyph(x, y, &path),
+            );
+        }
+        ret
+    }
+
+    fn glyph(&self, x: usize, y: usize, path: &[Pos]) -> Glyph {
+        let ch = char::from(b'0' + self.data[(y * self.width) + x]);
+        if path.contains(&Pos::new(x, y)) {
+            Glyph::new(ch, Color::Yellow)
+        } else {
+            ch.into()
+        }
+    }
+}
+
+/// Where the crucible stopped, and which way it went to get there. No direction at the start.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+struct State {
+    pos: Pos,
+    dir: Option<Dir>,
+}
+
+pub fn parse(data: &str) -> Map {
+    Map::from_input(data)
+}
+
+pub fn part1(map: &Map) -> u64 {
+    map.min_heat_loss(1, 3)
+}
+
+pub fn part2(map: &Map) -> u64 {
+    map.min_heat_loss(4, 10)
+}
+
+/// A square of random heat losses, at least 5 wide so the ultra crucible can reach the end.
+pub fn generate(side: usize, rng: &mut Rng) -> String {
+    let side = side.max(5);
+    let mut ret = String::new();
+    for _ in 0..side {
+        ret.extend((0..side).map(|_| char::from(b'1' + rng.below(9) as u8)));
+        ret.push('\n');
+    }
+    ret
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DATA1: &str = r#"
+2413432311323
+3215453535623
+3255245654254
+3446585845452
+4546657867536
+1438598798454
+4457876987766
+3637877979653
+4654967986887
+4564679986453
+1224686865563
+2546548887735
+4322674655533"#;
+
+    const DATA2: &str = r#"
+111111111111
+999999999991
+999999999991
+999999999991
+999999999991"#;
+
+    #[test]
+    fn day17_part1() {
+        assert_eq!(part1(&parse(DATA1)), 102);
+    }
+
+    #[test]
+    fn day17_part2() {
+        assert_eq!(part2(&parse(DATA1)), 94);
+        assert_eq!(part2(&parse(DATA2)), 71);
+    }
+}
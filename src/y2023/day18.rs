@@ -0,0 +1,265 @@
+use crate::{
+    debug,
+    grid::{self, Fill, Pos},
+    log::{self, Level},
+    parse,
+    vis::{self, Color, Glyph},
+};
+
+pub const TITLE: &str = "Lavaduct Lagoon";
+
+/// Bounding boxes up to this many cells get dug out on a grid as well, as a cross-check of the
+/// area computation.
+const MAX_GRID_CELLS: usize = 1 << 16;
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+enum Dir {
+    L,
+    R,
+    U,
+    D,
+}
+
+impl Dir {
+    fn from_input(input: &str) -> Self {
+        match input {
+            "L" => Self::L,
+            "R" => Self::R,
+            "U" => Self::U,
+            "D" => Self::D,
+            _ => panic!("unexpected direction {input:?}"),
+        }
+    }
+
+    /// The direction in the last digit of a colour.
+    fn from_digit(digit: u32) -> Self {
+        match digit {
+            0 => Self::R,
+            1 => Self::D,
+            2 => Self::L,
+            3 => Self::U,
+            _ => panic!("unexpected direction digit {digit}"),
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Step {
+    dir: Dir,
+    len: i64,
+    color: u32,
+}
+
+impl Step {
+    fn from_input(line: &str) -> Self {
+        let mut parts = line.split_whitespace();
+        let dir = Dir::from_input(parts.next().unwrap());
+        let len: u32 = parse::integer(parts.next().unwrap()).unwrap();
+        let color = parts
+            .next()
+            .and_then(|v| v.strip_prefix("(#"))
+            .and_then(|v| v.strip_suffix(')'))
+            .filter(|v| v.len() == 6)
+            .unwrap_or_else(|| panic!("{line:?}"));
+        assert_eq!(parts.next(), None, "{line:?}");
+
+        Self {
+            dir,
+            len: len.into(),
+            color: u32::from_str_radix(color, 16).unwrap(),
+        }
+    }
+
+    /// The real instruction hidden in the colour: five hex digits of length and one of direction.
+    fn decoded(self) -> Self {
+        Self {
+            dir: Dir::from_digit(self.color & 0xf),
+            len: (self.color >> 4).into(),
+            color: self.color,
+        }
+    }
+}
+
+/// Cubic metres of lava the lagoon holds: the trench plus everything it encloses. The shoelace
+/// formula gives the area of the polygon through the centres of the trench cells, and by Pick's
+/// theorem that area misses half of the boundary cells and one more.
+fn lagoon_volume(steps: impl IntoIterator<Item = Step>) -> i64 {
+    let (mut x, mut y) = (0i64, 0i64);
+    let mut area2 = 0;
+    let mut boundary = 0;
+    for step in steps {
+        let (nx, ny) = match step.dir {
+            Dir::L => (x - step.len, y),
+            Dir::R => (x + step.len, y),
+            Dir::U => (x, y - step.len),
+            Dir::D => (x, y + step.len),
+        };
+        area2 += (x * ny) - (nx * y);
+        boundary += step.len;
+        (x, y) = (nx, ny);
+    }
+    assert_eq!((x, y), (0, 0), "trench does not return to the start");
+    (area2.abs() + boundary) / 2 + 1
+}
+
+/// The lagoon dug out on a grid, for small plans.
+struct Grid {
+    data: Vec<u8>,
+    width: usize,
+}
+
+impl Grid {
+    /// Digs the trench with a free row and column around it, or `None` if the plan is too big.
+    fn dig(steps: &[Step]) -> Option<Self> {
+        let mut corners = vec![(0i64, 0i64)];
+        for step in steps {
+            let (x, y) = *corners.last().unwrap();
+            corners.push(match step.dir {
+                Dir::L => (x - step.len, y),
+                Dir::R => (x + step.len, y),
+                Dir::U => (x, y - step.len),
+                Dir::D => (x, y + step.len),
+            });
+        }
+        let min_x = corners.iter().map(|v| v.0).min().unwrap() - 1;
+        let min_y = corners.iter().map(|v| v.1).min().unwrap() - 1;
+        let width = usize::try_from(corners.iter().map(|v| v.0).max().unwrap() - min_x + 2).ok()?;
+        let height =
+            usize::try_from(corners.iter().map(|v| v.1).max().unwrap() - min_y + 2).ok()?;
+        if width.checked_mul(height)? > MAX_GRID_CELLS {
+            return None;
+        }
+
+        let mut ret = Self {
+            data: vec![b'.'; width * height],
+            width,
+        };
+        for pair in corners.windows(2) {
+            let (x1, x2) = (pair[0].0.min(pair[1].0), pair[0].0.max(pair[1].0));
+            let (y1, y2) = (pair[0].1.min(pair[1].1), pair[0].1.max(pair[1].1));
+            for y in y1..=y2 {
+                for x in x1..=x2 {
+                    let i = ((y - min_y) as usize * width) + (x - min_x) as usize;
+                    ret.data[i] = b'#';
+                }
+            }
+        }
+        Some(ret)
+    }
+
+    /// Cells not reached by filling from the corner.
+    fn volume(&mut self) -> usize {
+        grid::flood_fill(self, Pos { x: 0, y: 0 });
+        self.data.iter().filter(|&&v| v != b' ').count()
+    }
+
+    fn glyph(&self, x: usize, y: usize) -> Glyph {
+        match self.data[(y * self.width) + x] {
+            b'#' => Glyph::new('#', Color::Red),
+            b'.' => Glyph::new('#', Color::Yellow),
+            _ => ' '.into(),
+        }
+    }
+
+    fn show(&self, title: &str) {
+        let height = self.data.len() / self.width;
+        vis::frame(title, self.width, height, |x, y| self.glyph(x, y));
+    }
+}
+
+impl Fill for Grid {
+    fn can_fill(&self, pos: Pos) -> bool {
+        pos.index(self.width)
+            .and_then(|i| self.data.get(i))
+            .is_some_and(|&v| v == b'.')
+    }
+
+    fn try_fill(&mut self, pos: Pos) -> bool {
+        match pos.index(self.width).and_then(|i| self.data.get_mut(i)) {
+            Some(v @ b'.') => {
+                *v = b' ';
+                true
+            }
+            _ => false,
+        }
+    }
+}
+
+/// Compares the computed volume with a dug out grid when the plan is small enough. Only runs
+/// with debug logging or visualization on, and only reports a mismatch.
+fn cross_check(steps: &[Step], volume: i64) {
+    if !log::enabled(Level::Debug) && !vis::enabled() {
+        return;
+    }
+    let Some(mut grid) = Grid::dig(steps) else {
+        debug!("plan too big to cross-check on a grid");
+        return;
+    };
+    let filled = grid.volume();
+    grid.show(&format!("Lagoon holding {filled}"));
+    if i64::try_from(filled) == Ok(volume) {
+        debug!("flood fill agrees on {filled}");
+    } else {
+        debug!("MISMATCH: flood fill holds {filled}, shoelace {volume}");
+    }
+}
+
+pub fn parse(data: &str) -> Vec<Step> {
+    data.trim().lines().map(Step::from_input).collect()
+}
+
+pub fn part1(steps: &[Step]) -> i64 {
+    let ret = lagoon_volume(steps.iter().copied());
+    cross_check(steps, ret);
+    ret
+}
+
+pub fn part2(steps: &[Step]) -> i64 {
+    lagoon_volume(steps.iter().map(|v| v.decoded()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DATA1: &str = r#"
+R 6 (#70c710)
+D 5 (#0dc571)
+L 2 (#5713f0)
+D 2 (#d2c081)
+R 2 (#59c680)
+D 2 (#411b91)
+L 5 (#8ceee2)
+U 2 (#caa173)
+L 1 (#1b58a2)
+U 2 (#caa171)
+R 2 (#7807d2)
+U 3 (#a77fa3)
+L 2 (#015232)
+U 2 (#7a21e3)"#;
+
+    #[test]
+    fn day18_part1() {
+        assert_eq!(part1(&parse(DATA1)), 62);
+    }
+
+    #[test]
+    fn day18_part2() {
+        assert_eq!(part2(&parse(DATA1)), 952408144115);
+    }
+
+    #[test]
+    fn day18_flood_fill() {
+        let steps = parse(DATA1);
+        assert_eq!(Grid::dig(&steps).unwrap().volume(), 62);
+
+        let decoded: Vec<_> = steps.iter().map(|v| v.decoded()).collect();
+        assert!(Grid::dig(&decoded).is_none());
+    }
+
+    #[test]
+    #[should_panic(expected = "expected")]
+    fn day18_negative_length() {
+        parse("R -6 (#70c710)");
+    }
+}
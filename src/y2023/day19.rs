@@ -0,0 +1,245 @@
+use std::ops::Range;
+
+use crate::{debug, hash::FastMap, parse, range::RangeSet, trace};
+
+pub const TITLE: &str = "Aplenty";
+
+/// Ratings a part can have in every category.
+const RATINGS: Range<u64> = 1..4001;
+
+pub struct System<'a> {
+    workflows: FastMap<&'a str, Workflow<'a>>,
+    parts: Vec<[u64; 4]>,
+}
+
+impl<'a> System<'a> {
+    fn from_input(input: &'a str) -> Self {
+        let mut sections = parse::sections(input);
+        let mut workflows = FastMap::default();
+        for line in sections.next().unwrap().lines() {
+            let (name, workflow) = Workflow::from_input(line);
+            let prev = workflows.insert(name, workflow);
+            assert!(prev.is_none(), "{name} defined twice");
+        }
+        let parts = sections
+            .next()
+            .unwrap()
+            .lines()
+            .map(part_from_input)
+            .collect();
+        assert!(sections.next().is_none());
+
+        assert!(workflows.contains_key("in"), "no `in` workflow");
+        for workflow in workflows.values() {
+            let targets = workflow.rules.iter().map(|v| v.target);
+            for target in targets.chain([workflow.fallback]) {
+                if let Target::Workflow(name) = target {
+                    assert!(workflows.contains_key(name), "unknown workflow {name}");
+                }
+            }
+        }
+
+        Self { workflows, parts }
+    }
+
+    fn accepts(&self, part: &[u64; 4]) -> bool {
+        let mut name = "in";
+        loop {
+            let next = self.workflows[name].target(part);
+            trace!("{part:?}: {name} -> {next:?}");
+            match next {
+                Target::Accept => return true,
+                Target::Reject => return false,
+                Target::Workflow(next) => name = next,
+            }
+        }
+    }
+
+    /// How many combinations of ratings in `parts` are accepted, starting at workflow `name`.
+    fn count_accepted(&self, name: &str, mut parts: [RangeSet<u64>; 4]) -> u64 {
+        let workflow = &self.workflows[name];
+        let mut ret = 0;
+        for rule in &workflow.rules {
+            let matched = parts[rule.category].intersection(&rule.matching().into());
+            let mut next = parts.clone();
+            next[rule.category] = matched;
+            parts[rule.category].remove(rule.matching());
+            ret += self.count_target(name, rule.target, next);
+            if parts[rule.category].is_empty() {
+                return ret;
+            }
+        }
+        ret + self.count_target(name, workflow.fallback, parts)
+    }
+
+    fn count_target(&self, from: &str, target: Target, parts: [RangeSet<u64>; 4]) -> u64 {
+        if parts.iter().any(RangeSet::is_empty) {
+            return 0;
+        }
+        trace!("{from} -> {target:?} {parts:?}");
+        match target {
+            Target::Accept => parts.iter().map(RangeSet::len).product(),
+            Target::Reject => 0,
+            Target::Workflow(name) => self.count_accepted(name, parts),
+        }
+    }
+}
+
+struct Workflow<'a> {
+    rules: Vec<Rule<'a>>,
+    fallback: Target<'a>,
+}
+
+impl<'a> Workflow<'a> {
+    fn from_input(input: &'a str) -> (&'a str, Self) {
+        let (name, rules) = input
+            .strip_suffix('}')
+            .and_then(|v| v.split_once('{'))
+            .unwrap_or_else(|| panic!("{input:?}"));
+        let mut rules: Vec<_> = rules.split(',').collect();
+        let fallback = Target::from_input(rules.pop().unwrap());
+        let rules = rules.into_iter().map(Rule::from_input).collect();
+        (name, Self { rules, fallback })
+    }
+
+    fn target(&self, part: &[u64; 4]) -> Target<'a> {
+        self.rules
+            .iter()
+            .find(|rule| rule.matching().contains(&part[rule.category]))
+            .map_or(self.fallback, |rule| rule.target)
+    }
+}
+
+/// A condition like `a<2006:qkq`.
+struct Rule<'a> {
+    category: usize,
+    less: bool,
+    value: u64,
+    target: Target<'a>,
+}
+
+impl<'a> Rule<'a> {
+    fn from_input(input: &'a str) -> Self {
+        let (condition, target) = input.split_once(':').unwrap();
+        let category = category_index(&condition[..1]);
+        let less = match &condition[1..2] {
+            "<" => true,
+            ">" => false,
+            op => panic!("unexpected comparison {op:?}"),
+        };
+        Self {
+            category,
+            less,
+            value: parse::integer(&condition[2..]).unwrap(),
+            target: Target::from_input(target),
+        }
+    }
+
+    /// The ratings that satisfy the condition.
+    fn matching(&self) -> Range<u64> {
+        if self.less {
+            RATINGS.start..self.value.clamp(RATINGS.start, RATINGS.end)
+        } else {
+            (self.value + 1).clamp(RATINGS.start, RATINGS.end)..RATINGS.end
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+enum Target<'a> {
+    Accept,
+    Reject,
+    Workflow(&'a str),
+}
+
+impl<'a> Target<'a> {
+    fn from_input(input: &'a str) -> Self {
+        match input {
+            "A" => Self::Accept,
+            "R" => Self::Reject,
+            name => Self::Workflow(name),
+        }
+    }
+}
+
+fn category_index(input: &str) -> usize {
+    match input {
+        "x" => 0,
+        "m" => 1,
+        "a" => 2,
+        "s" => 3,
+        _ => panic!("unexpected category {input:?}"),
+    }
+}
+
+/// A part like `{x=787,m=2655,a=1222,s=2876}`, with the ratings in `xmas` order.
+fn part_from_input(input: &str) -> [u64; 4] {
+    let ratings = input
+        .strip_prefix('{')
+        .and_then(|v| v.strip_suffix('}'))
+        .unwrap_or_else(|| panic!("{input:?}"));
+    let mut ret = [None; 4];
+    for rating in ratings.split(',') {
+        let (category, value) = rating.split_once('=').unwrap();
+        let prev = ret[category_index(category)].replace(parse::integer(value).unwrap());
+        assert!(prev.is_none(), "{input:?}");
+    }
+    ret.map(|v| v.unwrap_or_else(|| panic!("missing rating in {input:?}")))
+}
+
+pub fn parse(data: &str) -> System<'_> {
+    System::from_input(data)
+}
+
+pub fn part1(system: &System) -> u64 {
+    system
+        .parts
+        .iter()
+        .filter(|part| system.accepts(part))
+        .map(|part| part.iter().sum::<u64>())
+        .sum()
+}
+
+pub fn part2(system: &System) -> u64 {
+    let all = [(); 4].map(|_| RangeSet::from(RATINGS));
+    let ret = system.count_accepted("in", all);
+    debug!(
+        "{} workflows accept {ret} combinations",
+        system.workflows.len()
+    );
+    ret
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DATA1: &str = r#"
+px{a<2006:qkq,m>2090:A,rfg}
+pv{a>1716:R,A}
+lnx{m>1548:A,A}
+rfg{s<537:gd,x>2440:R,A}
+qs{s>3448:A,lnx}
+qkq{x<1416:A,crn}
+crn{x>2662:A,R}
+in{s<1351:px,qqz}
+qqz{s>2770:qs,m<1801:hdj,R}
+gd{a>3333:R,R}
+hdj{m>838:A,pv}
+
+{x=787,m=2655,a=1222,s=2876}
+{x=1679,m=44,a=2067,s=496}
+{x=2036,m=264,a=79,s=2244}
+{x=2461,m=1339,a=466,s=291}
+{x=2127,m=1623,a=2188,s=1013}"#;
+
+    #[test]
+    fn day19_part1() {
+        assert_eq!(part1(&parse(DATA1)), 19114);
+    }
+
+    #[test]
+    fn day19_part2() {
+        assert_eq!(part2(&parse(DATA1)), 167409079868000);
+    }
+}
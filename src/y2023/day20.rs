@@ -0,0 +1,285 @@
+use std::collections::VecDeque;
+
+use crate::{debug, hash::FastMap, math, trace};
+
+pub const TITLE: &str = "Pulse Propagation";
+
+/// Presses to try in part 2 before giving up on finding the cycles.
+const MAX_PRESSES: usize = 1 << 16;
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+enum Kind {
+    Broadcaster,
+    FlipFlop,
+    Conjunction,
+    /// Only receives pulses, like `output` or `rx`.
+    Sink,
+}
+
+struct Module {
+    kind: Kind,
+    outputs: Vec<usize>,
+    inputs: Vec<usize>,
+}
+
+pub struct Network<'a> {
+    names: Vec<&'a str>,
+    modules: Vec<Module>,
+    broadcaster: usize,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+struct Pulse {
+    from: usize,
+    to: usize,
+    high: bool,
+}
+
+/// What the modules remember between pulses.
+struct State {
+    on: Vec<bool>,
+    /// The last pulse from every input, in the order of `Module::inputs`.
+    memory: Vec<Vec<bool>>,
+}
+
+impl<'a> Network<'a> {
+    fn from_input(input: &'a str) -> Self {
+        let mut ret = Self {
+            names: Vec::new(),
+            modules: Vec::new(),
+            broadcaster: 0,
+        };
+        let mut ids = FastMap::default();
+        let lines: Vec<_> = input
+            .trim()
+            .lines()
+            .map(|line| {
+                let (name, outputs) = line.split_once(" -> ").unwrap();
+                let (kind, name) = match name.as_bytes()[0] {
+                    b'%' => (Kind::FlipFlop, &name[1..]),
+                    b'&' => (Kind::Conjunction, &name[1..]),
+                    _ if name == "broadcaster" => (Kind::Broadcaster, name),
+                    _ => panic!("unexpected module {name:?}"),
+                };
+                let id = ret.add(&mut ids, name);
+                assert_eq!(ret.modules[id].kind, Kind::Sink, "{name} defined twice");
+                ret.modules[id].kind = kind;
+                (id, outputs)
+            })
+            .collect();
+
+        for (id, outputs) in lines {
+            for output in outputs.split(", ") {
+                let output = ret.add(&mut ids, output);
+                ret.modules[id].outputs.push(output);
+                ret.modules[output].inputs.push(id);
+            }
+        }
+        ret.broadcaster = *ids.get("broadcaster").expect("no broadcaster");
+        ret
+    }
+
+    fn add(&mut self, ids: &mut FastMap<&'a str, usize>, name: &'a str) -> usize {
+        *ids.entry(name).or_insert_with(|| {
+            self.names.push(name);
+            self.modules.push(Module {
+                kind: Kind::Sink,
+                outputs: Vec::new(),
+                inputs: Vec::new(),
+            });
+            self.modules.len() - 1
+        })
+    }
+
+    fn id(&self, name: &str) -> Option<usize> {
+        self.names.iter().position(|&v| v == name)
+    }
+
+    fn initial_state(&self) -> State {
+        State {
+            on: vec![false; self.modules.len()],
+            memory: self
+                .modules
+                .iter()
+                .map(|v| vec![false; v.inputs.len()])
+                .collect(),
+        }
+    }
+
+    /// Pushes the button once, handling pulses in the order they are sent.
+    fn press(&self, state: &mut State, mut on_pulse: impl FnMut(Pulse)) {
+        let mut pending = VecDeque::from([Pulse {
+            from: self.broadcaster,
+            to: self.broadcaster,
+            high: false,
+        }]);
+
+        while let Some(pulse) = pending.pop_front() {
+            trace!(
+                "{} -{}-> {}",
+                self.names[pulse.from],
+                if pulse.high { "high" } else { "low" },
+                self.names[pulse.to]
+            );
+            on_pulse(pulse);
+
+            let module = &self.modules[pulse.to];
+            let high = match module.kind {
+                Kind::Broadcaster => pulse.high,
+                Kind::FlipFlop => {
+                    if pulse.high {
+                        continue;
+                    }
+                    let on = &mut state.on[pulse.to];
+                    *on = !*on;
+                    *on
+                }
+                Kind::Conjunction => {
+                    let slot = module.inputs.iter().position(|&v| v == pulse.from).unwrap();
+                    let memory = &mut state.memory[pulse.to];
+                    memory[slot] = pulse.high;
+                    !memory.iter().all(|&v| v)
+                }
+                Kind::Sink => continue,
+            };
+            pending.extend(module.outputs.iter().map(|&to| Pulse {
+                from: pulse.to,
+                to,
+                high,
+            }));
+        }
+    }
+
+    /// The conjunction feeding `rx`. Its inputs are conjunctions too, each sending a high pulse
+    /// on a fixed cycle of presses, so `rx` gets a low pulse when all cycles line up.
+    fn rx_feeder(&self) -> usize {
+        let rx = self.id("rx").expect("no rx module");
+        let [feeder] = self.modules[rx].inputs[..] else {
+            panic!("rx should have exactly one input");
+        };
+        let is_conjunction = |id: usize| self.modules[id].kind == Kind::Conjunction;
+        assert!(is_conjunction(feeder), "rx should be fed by a conjunction");
+        for &input in &self.modules[feeder].inputs {
+            assert!(
+                is_conjunction(input),
+                "{} feeds {} but is not a conjunction",
+                self.names[input],
+                self.names[feeder]
+            );
+        }
+        feeder
+    }
+}
+
+pub fn parse(data: &str) -> Network<'_> {
+    Network::from_input(data)
+}
+
+pub fn part1(network: &Network) -> usize {
+    let mut state = network.initial_state();
+    let mut counts = [0; 2];
+    for _ in 0..1000 {
+        network.press(&mut state, |pulse| counts[usize::from(pulse.high)] += 1);
+    }
+    debug!("{} low and {} high pulses", counts[0], counts[1]);
+    counts[0] * counts[1]
+}
+
+pub fn part2(network: &Network) -> usize {
+    let feeder = network.rx_feeder();
+    let inputs = &network.modules[feeder].inputs;
+    // The presses on which every input first sent a high pulse to the feeder, and then again
+    let mut highs = vec![Vec::new(); inputs.len()];
+    let mut state = network.initial_state();
+
+    for presses in 1..=MAX_PRESSES {
+        network.press(&mut state, |pulse| {
+            if pulse.high && pulse.to == feeder {
+                let slot = inputs.iter().position(|&v| v == pulse.from).unwrap();
+                if highs[slot].last() != Some(&presses) {
+                    highs[slot].push(presses);
+                }
+            }
+        });
+        if highs.iter().all(|v| v.len() >= 2) {
+            break;
+        }
+    }
+
+    let cycles: Vec<usize> = highs
+        .iter()
+        .zip(inputs)
+        .map(|(highs, &input)| {
+            let name = network.names[input];
+            let [first, second, ..] = highs[..] else {
+                panic!("no cycle for {name} within {MAX_PRESSES} presses");
+            };
+            // The cycles have to start at the beginning for them to line up at their LCM
+            assert_eq!(second, 2 * first, "{name} is high after {highs:?} presses");
+            debug!("{name} is high every {first} presses");
+            first
+        })
+        .collect();
+
+    math::checked_lcm_all(&cycles).expect("press count overflows usize")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DATA1: &str = r#"
+broadcaster -> a, b, c
+%a -> b
+%b -> c
+%c -> inv
+&inv -> a"#;
+
+    const DATA2: &str = r#"
+broadcaster -> a
+%a -> inv, con
+&inv -> b
+%b -> con
+&con -> output"#;
+
+    /// A counter resetting every 3 presses and one flipping every 2 presses, feeding rx.
+    const DATA3: &str = r#"
+broadcaster -> a, x
+%a -> b, c
+%b -> c
+&c -> a, n
+&n -> hub
+%x -> m
+&m -> hub
+&hub -> rx"#;
+
+    #[test]
+    fn day20_part1() {
+        assert_eq!(part1(&parse(DATA1)), 32000000);
+        assert_eq!(part1(&parse(DATA2)), 11687500);
+    }
+
+    #[test]
+    fn day20_part2() {
+        let network = parse(DATA3);
+        assert_eq!(part2(&network), 6);
+
+        // Agrees with pressing until rx gets a low pulse
+        let rx = network.id("rx").unwrap();
+        let mut state = network.initial_state();
+        let presses = (1..)
+            .find(|_| {
+                let mut low = false;
+                network.press(&mut state, |pulse| low |= pulse.to == rx && !pulse.high);
+                low
+            })
+            .unwrap();
+        assert_eq!(presses, 6);
+    }
+
+    #[test]
+    #[should_panic(expected = "no rx module")]
+    fn day20_part2_needs_rx() {
+        part2(&parse(DATA1));
+    }
+}
@@ -0,0 +1,45 @@
+pub mod day01;
+pub mod day02;
+pub mod day03;
+pub mod day04;
+pub mod day05;
+pub mod day06;
+pub mod day07;
+pub mod day08;
+pub mod day09;
+pub mod day10;
+pub mod day11;
+pub mod day12;
+pub mod day13;
+pub mod day14;
+pub mod day15;
+pub mod day16;
+pub mod day17;
+pub mod day18;
+pub mod day19;
+pub mod day20;
+
+use crate::days::{day, Day};
+
+pub static DAYS: &[Day] = &[
+    day!(2023, 1, day01, explain, generate),
+    day!(2023, 2, day02, validate),
+    day!(2023, 3, day03, parse),
+    day!(2023, 4, day04, parse, explain),
+    day!(2023, 5, day05, parse, repl),
+    day!(2023, 6, day06, validate),
+    day!(2023, 7, day07, repl, explain, validate),
+    day!(2023, 8, day08, parse, repl),
+    day!(2023, 9, day09, parallel, validate, generate),
+    day!(2023, 10, day10, parse, format),
+    day!(2023, 11, day11, validate, format, generate),
+    day!(2023, 12, day12, parallel, validate),
+    day!(2023, 13, day13, parse, format),
+    day!(2023, 14, day14, parse, format),
+    day!(2023, 15, day15, validate),
+    day!(2023, 16, day16, parse, format, generate),
+    day!(2023, 17, day17, parse, format, generate),
+    day!(2023, 18, day18, parse),
+    day!(2023, 19, day19, parse),
+    day!(2023, 20, day20, parse),
+];